@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+
+use crate::global::input::InputAction;
+use crate::global::input::InputMap;
+use crate::global::input::PlayerSlot;
+use crate::menu::navigation::MenuFocus;
+use crate::menu::MenuAppExt;
+use crate::menu::MenuBuilder;
+use crate::menu::OVERLAY_MENU_LAYER;
+use crate::settings::transition::SettingsState;
+use crate::settings::transition::SettingsStateTransitionEvent;
+use crate::GameSystemSet;
+
+const BINDING_AWAITING_COLOR: Color = Color::GOLD;
+
+const TITLE_TEXT: &str = "CONTROLS";
+const BINDING_AWAITING_TEXT: &str = "PRESS A KEY OR BUTTON...";
+const BINDING_UNBOUND_TEXT: &str = "-";
+const PLAYER_BUTTON_TEXT: &str = "KEYS OF";
+const RESET_BUTTON_TEXT: &str = "RESET TO DEFAULTS";
+const BACK_BUTTON_TEXT: &str = "BACK";
+
+#[derive(Component, Debug)]
+pub struct BindingButtonText(InputAction);
+
+#[derive(Component, Debug)]
+pub struct PlayerButtonText;
+
+/// Player whose keys are shown and bound. The gamepad buttons are shared by
+/// all the players.
+#[derive(Resource, Debug, Default)]
+pub struct SelectedPlayer(PlayerSlot);
+
+/// Action waiting for the player to press the key or gamepad button to bind.
+#[derive(Resource, Debug, Default)]
+pub struct AwaitingBinding(Option<InputAction>);
+
+pub struct ControlsUIPlugin;
+
+impl Plugin for ControlsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AwaitingBinding>()
+            .init_resource::<SelectedPlayer>()
+            .add_menu(SettingsState::Controls, controls_menu)
+            .add_systems(OnExit(SettingsState::Controls), unlock_menu_focus)
+            .add_systems(
+                Update,
+                (capture_binding, redraw_bindings)
+                    .chain()
+                    .in_set(GameSystemSet::Controls),
+            );
+    }
+}
+
+fn controls_menu(
+    mut awaiting: ResMut<AwaitingBinding>,
+    mut selected_player: ResMut<SelectedPlayer>,
+) -> MenuBuilder {
+    awaiting.0 = None;
+    selected_player.0 = PlayerSlot::One;
+    let menu = MenuBuilder::new("controls", TITLE_TEXT)
+        .layer(OVERLAY_MENU_LAYER)
+        .dense()
+        .label_button(PlayerButtonText, |cmd| {
+            cmd.add(|world: &mut World| {
+                world.resource_mut::<AwaitingBinding>().0 = None;
+                let mut selected_player = world.resource_mut::<SelectedPlayer>();
+                selected_player.0 = match selected_player.0 {
+                    PlayerSlot::One => PlayerSlot::Two,
+                    PlayerSlot::Two => PlayerSlot::One,
+                };
+            });
+        });
+    InputAction::ALL
+        .into_iter()
+        .fold(menu, |menu, action| {
+            menu.label_button(BindingButtonText(action), move |cmd| {
+                cmd.add(move |world: &mut World| {
+                    let mut awaiting = world.resource_mut::<AwaitingBinding>();
+                    // pressing the awaiting binding again cancels it
+                    awaiting.0 = match awaiting.0 {
+                        Some(awaiting) if awaiting == action => None,
+                        _ => Some(action),
+                    };
+                });
+            })
+        })
+        .button(RESET_BUTTON_TEXT, |cmd| {
+            cmd.add(|world: &mut World| {
+                world.resource_mut::<AwaitingBinding>().0 = None;
+                world.resource_mut::<InputMap>().reset();
+            });
+        })
+        .event_button(BACK_BUTTON_TEXT, SettingsStateTransitionEvent::Back)
+}
+
+fn unlock_menu_focus(mut menu_focus: ResMut<MenuFocus>) {
+    menu_focus.locked = false;
+}
+
+fn capture_binding(
+    selected_player: Res<SelectedPlayer>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut input_map: ResMut<InputMap>,
+    mut awaiting: ResMut<AwaitingBinding>,
+    mut menu_focus: ResMut<MenuFocus>,
+) {
+    // the key or button that started the binding must not be captured
+    let just_started = awaiting.is_changed();
+    menu_focus.locked = awaiting.0.is_some();
+    let Some(action) = awaiting.0 else {
+        return;
+    };
+    if just_started {
+        return;
+    }
+    if let Some(key) = keyboard_input.get_just_pressed().next() {
+        input_map.bind_key(selected_player.0, action, *key);
+    } else if let Some(button) = gamepad_buttons.get_just_pressed().next() {
+        input_map.bind_gamepad_button(action, button.button_type);
+    } else {
+        return;
+    }
+    awaiting.0 = None;
+}
+
+fn redraw_bindings(
+    input_map: Res<InputMap>,
+    awaiting: Res<AwaitingBinding>,
+    selected_player: Res<SelectedPlayer>,
+    mut buttons_query: Query<&mut Style, With<Button>>,
+    mut text_query: Query<(&mut Text, &BindingButtonText, &Parent), Without<PlayerButtonText>>,
+    mut player_text_query: Query<&mut Text, With<PlayerButtonText>>,
+) {
+    let slot = selected_player.0;
+    for mut text in player_text_query.iter_mut() {
+        text.sections[0].value = format!("{} {}", PLAYER_BUTTON_TEXT, slot.title());
+    }
+    for (mut text, binding_text, parent) in text_query.iter_mut() {
+        let action = binding_text.0;
+        // the actions without a key for the player are hidden
+        if let Ok(mut style) = buttons_query.get_mut(parent.get()) {
+            style.display = if input_map.key(slot, action).is_some() {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+        let section = &mut text.sections[0];
+        if awaiting.0 == Some(action) {
+            section.value = format!("{}: {}", action.title(), BINDING_AWAITING_TEXT);
+            section.style.color = BINDING_AWAITING_COLOR;
+        } else {
+            let key = input_map
+                .key(slot, action)
+                .map_or(BINDING_UNBOUND_TEXT.to_string(), |key| format!("{:?}", key));
+            let gamepad_button = input_map
+                .gamepad_button(action)
+                .map_or(BINDING_UNBOUND_TEXT.to_string(), |button| {
+                    format!("{:?}", button)
+                });
+            section.value = format!("{}: {} / {}", action.title(), key, gamepad_button);
+            section.style.color = Color::WHITE;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_prng::WyRand;
+use bevy_rand::resource::GlobalEntropy;
+
+use super::pickup::PowerUpKind;
+use super::pickup::PowerUps;
+use super::turret::Aim;
+use crate::encounter::component::weapon::beam::BeamWeapon;
+use crate::encounter::component::weapon::HitEvent;
+use crate::encounter::component::weapon::Shooter;
+use crate::encounter::component::*;
+use crate::global::input::InputAction;
+use crate::global::input::PlayerActions;
+use crate::global::input::PlayerSlot;
+use crate::state::GameState;
+use crate::GameSystemSet;
+
+#[derive(Component, Debug, Default)]
+pub struct Tag;
+
+#[derive(Bundle, Default)]
+pub struct BeamBundle {
+    sprite: SpriteBundle,
+    tag: Tag,
+    name: Name,
+}
+
+impl BeamBundle {
+    pub fn new(sprite: SpriteBundle) -> Self {
+        Self {
+            sprite,
+            name: Name::new("Beam"),
+            ..default()
+        }
+    }
+}
+
+pub struct BeamPlugin;
+impl Plugin for BeamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            beam_controls_handler.in_set(GameSystemSet::EncounterPausable),
+        )
+        .add_systems(OnExit(GameState::Encounter), despawn_beams);
+    }
+}
+
+fn beam_controls_handler(
+    mut cmd: Commands,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    mut evw_hit: EventWriter<HitEvent>,
+    global_timer: Res<Time>,
+    player_actions: Res<PlayerActions>,
+    mut player_query: Query<
+        (
+            Entity,
+            &PlayerSlot,
+            &Transform,
+            &Aim,
+            &mut BeamWeapon,
+            &PowerUps,
+        ),
+        (
+            With<super::player::Tag>,
+            Without<super::player::Down>,
+            Without<Tag>,
+        ),
+    >,
+    enemies_query: Query<
+        (Entity, &Transform, &EntitySize),
+        (
+            With<super::enemy::Tag>,
+            Without<super::enemy::Dead>,
+            Without<Tag>,
+        ),
+    >,
+    mut health_query: Query<(&mut Health, &Parent), With<super::health::Tag>>,
+    mut beams_query: Query<(Entity, &Shooter, &mut Transform, &mut Sprite), With<Tag>>,
+) {
+    // beams of the players who lost the weapon or went down
+    for (beam_entity, shooter, _, _) in beams_query.iter() {
+        if !player_query.contains(shooter.0) {
+            cmd.entity(beam_entity).despawn_recursive();
+        }
+    }
+
+    for (player_entity, player_slot, player_transform, player_aim, mut beam_weapon, power_ups) in
+        player_query.iter_mut()
+    {
+        beam_weapon.tick(&global_timer);
+
+        let beam = beams_query
+            .iter_mut()
+            .find(|(_, shooter, _, _)| shooter.0 == player_entity);
+        let actions = player_actions.buttons(*player_slot);
+        if !actions.pressed(InputAction::Fire) || !beam_weapon.fire(&global_timer) {
+            if let Some((beam_entity, _, _, _)) = beam {
+                cmd.entity(beam_entity).despawn_recursive();
+            }
+            continue;
+        }
+
+        let (hit, length) = beam_weapon.raycast(
+            player_transform.translation,
+            player_aim.rotation() * Vec3::Y,
+            enemies_query
+                .iter()
+                .map(|(enemy_entity, enemy_transform, enemy_size)| {
+                    (enemy_entity, enemy_transform.translation, enemy_size.vec)
+                }),
+        );
+        if let Some((enemy_entity, enemy_translation)) = hit {
+            let (mut damage, critical) = beam_weapon.damage(&global_timer, &mut rng);
+            if power_ups.is_active(PowerUpKind::DoubleDamage) {
+                damage *= 2.;
+            }
+            for (mut health, parent) in health_query.iter_mut() {
+                if parent.get() == enemy_entity {
+                    health.actual -= damage;
+                }
+            }
+            evw_hit.send(HitEvent {
+                target: enemy_entity,
+                shooter: player_entity,
+                translation: enemy_translation,
+                damage,
+                critical,
+                explosive: false,
+            });
+        }
+
+        // the beam sprite is anchored at its bottom, so it only needs to be
+        // placed at the player and stretched to the hit point
+        let beam_transform = player_aim.shot_transform(player_transform.translation);
+        match beam {
+            Some((_, _, mut transform, mut sprite)) => {
+                *transform = beam_transform;
+                sprite.custom_size = Some(Vec2::new(beam_weapon.width(), length));
+            }
+            None => {
+                let mut sprite_bundle = beam_weapon.sprite_bundle();
+                sprite_bundle.transform = beam_transform;
+                sprite_bundle.sprite.custom_size = Some(Vec2::new(beam_weapon.width(), length));
+                cmd.spawn(BeamBundle::new(sprite_bundle))
+                    .insert(Shooter(player_entity));
+            }
+        }
+    }
+}
+
+fn despawn_beams(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
+    for beam in query.iter() {
+        cmd.entity(beam).despawn_recursive();
+    }
+}
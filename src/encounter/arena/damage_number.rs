@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::encounter::component::weapon::HitEvent;
+use crate::global::settings::GameSettings;
+use crate::state::GameState;
+use crate::GameSystemSet;
+
+const DAMAGE_NUMBER_LIFETIME_SECS: f32 = 0.8;
+/// Hits on the same target within this window are added to the existing
+/// number instead of spawning a new one.
+const DAMAGE_NUMBER_MERGE_WINDOW_SECS: f32 = 0.3;
+const DAMAGE_NUMBER_DRIFT_SPEED: f32 = 40.;
+const DAMAGE_NUMBER_Z: f32 = 10.;
+const DAMAGE_NUMBER_TEXT_SIZE: f32 = 18.;
+const DAMAGE_NUMBER_CRITICAL_TEXT_SIZE: f32 = 26.;
+const DAMAGE_NUMBER_COLOR: Color = Color::WHITE;
+const DAMAGE_NUMBER_EXPLOSIVE_COLOR: Color = Color::ORANGE;
+const DAMAGE_NUMBER_CRITICAL_COLOR: Color = Color::YELLOW;
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct DamageNumberSettings {
+    pub enabled: bool,
+}
+
+impl Default for DamageNumberSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct DamageNumber {
+    target: Entity,
+    damage: f32,
+    critical: bool,
+    explosive: bool,
+    lifetime: Timer,
+}
+
+impl DamageNumber {
+    fn new(hit: &HitEvent) -> Self {
+        Self {
+            target: hit.target,
+            damage: hit.damage,
+            critical: hit.critical,
+            explosive: hit.explosive,
+            lifetime: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME_SECS, TimerMode::Once),
+        }
+    }
+
+    fn mergeable(&self) -> bool {
+        self.lifetime.elapsed_secs() < DAMAGE_NUMBER_MERGE_WINDOW_SECS
+    }
+
+    /// Keeps the lifetime, so a continuous stream of hits (like the beam)
+    /// spawns a new number after each merge window instead of one that never
+    /// fades.
+    fn merge(&mut self, hit: &HitEvent) {
+        self.damage += hit.damage;
+        self.critical |= hit.critical;
+        self.explosive |= hit.explosive;
+    }
+
+    fn text(&self) -> Text {
+        let (value, font_size, color) = match (self.critical, self.explosive) {
+            (true, _) => (
+                format!("{:.0}!", self.damage),
+                DAMAGE_NUMBER_CRITICAL_TEXT_SIZE,
+                DAMAGE_NUMBER_CRITICAL_COLOR,
+            ),
+            (false, true) => (
+                format!("{:.0}", self.damage),
+                DAMAGE_NUMBER_TEXT_SIZE,
+                DAMAGE_NUMBER_EXPLOSIVE_COLOR,
+            ),
+            (false, false) => (
+                format!("{:.0}", self.damage),
+                DAMAGE_NUMBER_TEXT_SIZE,
+                DAMAGE_NUMBER_COLOR,
+            ),
+        };
+        Text::from_section(value, TextStyle {
+            font_size,
+            color,
+            ..default()
+        })
+    }
+}
+
+#[derive(Bundle)]
+pub struct DamageNumberBundle {
+    damage_number: DamageNumber,
+    text: Text2dBundle,
+    name: Name,
+}
+
+impl DamageNumberBundle {
+    fn new(hit: &HitEvent) -> Self {
+        let damage_number = DamageNumber::new(hit);
+        let text = Text2dBundle {
+            text: damage_number.text(),
+            transform: Transform::from_translation(
+                hit.translation.truncate().extend(DAMAGE_NUMBER_Z),
+            ),
+            ..default()
+        };
+        Self {
+            damage_number,
+            text,
+            name: Name::new("Damage Number"),
+        }
+    }
+}
+
+pub struct DamageNumberPlugin;
+impl Plugin for DamageNumberPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageNumberSettings>()
+            .register_type::<DamageNumberSettings>()
+            .add_systems(
+                Update,
+                (spawn_damage_numbers, animate_damage_numbers)
+                    .chain()
+                    .in_set(GameSystemSet::EncounterPausable),
+            )
+            .add_systems(OnExit(GameState::Encounter), despawn_damage_numbers)
+            .add_systems(Update, apply_settings.in_set(GameSystemSet::Global));
+    }
+}
+
+fn apply_settings(game_settings: Res<GameSettings>, mut settings: ResMut<DamageNumberSettings>) {
+    if game_settings.is_changed() {
+        settings.enabled = game_settings.damage_numbers;
+    }
+}
+
+fn spawn_damage_numbers(
+    mut cmd: Commands,
+    settings: Res<DamageNumberSettings>,
+    mut evr_hit: EventReader<HitEvent>,
+    mut damage_numbers_query: Query<(&mut DamageNumber, &mut Text)>,
+) {
+    if !settings.enabled {
+        evr_hit.clear();
+        return;
+    }
+    // hits landed within the same frame are not spawned yet, so they are
+    // merged here before touching the world
+    let mut pending: HashMap<Entity, DamageNumberBundle> = HashMap::new();
+    'hits: for hit in evr_hit.read() {
+        for (mut damage_number, mut text) in damage_numbers_query.iter_mut() {
+            if damage_number.target == hit.target && damage_number.mergeable() {
+                damage_number.merge(hit);
+                *text = damage_number.text();
+                continue 'hits;
+            }
+        }
+        match pending.get_mut(&hit.target) {
+            Some(bundle) => {
+                bundle.damage_number.merge(hit);
+                bundle.text.text = bundle.damage_number.text();
+            }
+            None => {
+                pending.insert(hit.target, DamageNumberBundle::new(hit));
+            }
+        }
+    }
+    for (_, bundle) in pending.into_iter() {
+        cmd.spawn(bundle);
+    }
+}
+
+fn animate_damage_numbers(
+    mut cmd: Commands,
+    timer: Res<Time>,
+    mut damage_numbers_query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut damage_number, mut transform, mut text) in damage_numbers_query.iter_mut() {
+        damage_number.lifetime.tick(timer.delta());
+        if damage_number.lifetime.finished() {
+            cmd.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation.y += DAMAGE_NUMBER_DRIFT_SPEED * timer.delta_seconds();
+        let alpha = 1. - damage_number.lifetime.fraction();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
+
+fn despawn_damage_numbers(mut cmd: Commands, query: Query<Entity, With<DamageNumber>>) {
+    for damage_number in query.iter() {
+        cmd.entity(damage_number).despawn_recursive();
+    }
+}
@@ -8,13 +8,26 @@ use rand::Rng;
 use super::health::HealthBundle;
 use super::EncounterSetupSystemSet;
 use crate::encounter::component::*;
+use crate::global::main_camera::CameraShakeEvent;
 use crate::state::GameState;
-use crate::transition::GameStateTransitionEvent;
 use crate::GameSystemSet;
 
 #[derive(Component, Debug, Default)]
 pub struct Tag;
 
+/// Marks an enemy killed, crashed into a player or touched down. It is
+/// ignored by the handlers from then on and despawned once at the end of the
+/// frame.
+#[derive(Component, Debug, Default)]
+pub struct Dead;
+
+#[derive(Event, Debug)]
+pub struct EnemyDeathEvent {
+    pub translation: Vec3,
+    /// Player whose hit killed the enemy.
+    pub killer: Option<Entity>,
+}
+
 #[derive(Bundle, Default)]
 pub struct EnemyBundle {
     size: EntitySize,
@@ -99,8 +112,17 @@ impl Plugin for EnemyPlugin {
         )
         .add_systems(
             Update,
-            game_over_swarm_criteria_handler.in_set(GameSystemSet::EncounterPausable),
-        );
+            (enemy_contact_handler, enemy_touchdown_handler)
+                .chain()
+                .after(move_enemies)
+                .in_set(GameSystemSet::EncounterPausable),
+        )
+        .add_systems(
+            PostUpdate,
+            despawn_dead_enemies.in_set(GameSystemSet::EncounterPausable),
+        )
+        .add_systems(OnExit(GameState::Encounter), despawn_enemy_swarm)
+        .add_event::<EnemyDeathEvent>();
     }
 }
 
@@ -109,6 +131,12 @@ const ENEMY_HORIZONTAL_SPEED_MAX: f32 = 100.;
 const ENEMY_VERTICAL_SPEED_MIN: f32 = 5.;
 const ENEMY_VERTICAL_SPEED_MAX: f32 = 15.;
 const ENEMY_SWARM_COUNT: usize = 50;
+/// Damage dealt to the player by an enemy crashing into them.
+const ENEMY_CONTACT_DAMAGE: f32 = 25.;
+/// Damage dealt to the base by an enemy reaching the ground.
+const ENEMY_TOUCHDOWN_DAMAGE: f32 = 10.;
+const ENEMY_CONTACT_SHAKE: f32 = 12.;
+const ENEMY_TOUCHDOWN_SHAKE: f32 = 6.;
 fn spawn_enemy_swarm_bundle(
     windows: Query<&Window, With<PrimaryWindow>>,
     mut cmd: Commands,
@@ -129,6 +157,18 @@ fn spawn_enemy_swarm_bundle(
     }
 }
 
+fn despawn_enemy_swarm(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
+    for enemy in query.iter() {
+        cmd.entity(enemy).despawn_recursive();
+    }
+}
+
+fn despawn_dead_enemies(mut cmd: Commands, query: Query<Entity, (With<Tag>, With<Dead>)>) {
+    for enemy in query.iter() {
+        cmd.entity(enemy).despawn_recursive();
+    }
+}
+
 fn move_enemies(
     timer: Res<Time>,
     mut query: Query<
@@ -164,17 +204,62 @@ fn move_enemies(
     );
 }
 
-fn game_over_swarm_criteria_handler(
+fn enemy_contact_handler(
+    mut cmd: Commands,
+    mut evw_shake: EventWriter<CameraShakeEvent>,
+    mut players_query: Query<
+        (&Transform, &EntitySize, &super::player::Dash, &mut Health),
+        (
+            With<super::player::Tag>,
+            Without<super::player::Down>,
+            Without<super::player::Invulnerable>,
+        ),
+    >,
+    enemies_query: Query<(Entity, &Transform, &EntitySize), (With<Tag>, Without<Dead>)>,
+) {
+    for (enemy_entity, enemy_transform, enemy_size) in enemies_query.iter() {
+        for (player_transform, player_size, player_dash, mut player_health) in
+            players_query.iter_mut()
+        {
+            if player_dash.invulnerable() {
+                continue;
+            }
+            let distance = (player_transform.translation - enemy_transform.translation)
+                .truncate()
+                .abs();
+            let reach = (player_size.vec + enemy_size.vec) / 2.;
+            if distance.x > reach.x || distance.y > reach.y {
+                continue;
+            }
+            player_health.actual -= ENEMY_CONTACT_DAMAGE;
+            cmd.entity(enemy_entity).insert(Dead);
+            evw_shake.send(CameraShakeEvent {
+                amplitude: ENEMY_CONTACT_SHAKE,
+            });
+            break;
+        }
+    }
+}
+
+fn enemy_touchdown_handler(
     windows: Query<&Window, With<PrimaryWindow>>,
-    mut evw_transition: EventWriter<GameStateTransitionEvent>,
-    enemy_entities_query: Query<(&Transform, &Name), With<super::enemy::Tag>>,
+    mut cmd: Commands,
+    mut evw_shake: EventWriter<CameraShakeEvent>,
+    mut ground_query: Query<&mut Health, (With<super::Tag>, Without<Tag>)>,
+    enemies_query: Query<(Entity, &Transform), (With<Tag>, Without<Dead>)>,
 ) {
     let window = windows.get_single().expect("Expected primary window");
+    let Ok(mut ground_health) = ground_query.get_single_mut() else {
+        return;
+    };
     let touchdown_y = super::battle_arena_bottom_bound(window) + ENEMY_ENTITY_HEIGHT / 2.;
-    if enemy_entities_query
-        .iter()
-        .any(|(entity_transform, _name)| entity_transform.translation.y <= touchdown_y)
-    {
-        evw_transition.send(GameStateTransitionEvent::GameOver);
+    for (enemy_entity, enemy_transform) in enemies_query.iter() {
+        if enemy_transform.translation.y <= touchdown_y {
+            ground_health.actual -= ENEMY_TOUCHDOWN_DAMAGE;
+            cmd.entity(enemy_entity).insert(Dead);
+            evw_shake.send(CameraShakeEvent {
+                amplitude: ENEMY_TOUCHDOWN_SHAKE,
+            });
+        }
     }
 }
@@ -1,33 +1,49 @@
+pub mod beam;
+pub mod damage_number;
 pub mod enemy;
 pub mod health;
+pub mod pickup;
 pub mod player;
 pub mod projectile;
+pub mod turret;
 pub mod ui;
 
+use beam::BeamPlugin;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use bevy::window::PrimaryWindow;
+use damage_number::DamageNumberPlugin;
 use enemy::EnemyPlugin;
+use pickup::PickupPlugin;
 use player::PlayerPlugin;
+use turret::TurretPlugin;
 use ui::ArenaUIPlugin;
 
 use self::projectile::ProjectilePlugin;
+use super::component::Health;
 use super::EncounterSetupSystemSet;
+use crate::game_over::GameOverReason;
 use crate::state::GameState;
+use crate::transition::GameStateTransitionEvent;
 use crate::util::ColorHex;
+use crate::GameSystemSet;
 
 pub const ARENA_LAYOUT_GROUND_HEIGHT_PERCENT: f32 = 0.05;
 pub const ARENA_LAYOUT_BATTLE_ARENA_HEIGHT_PERCENT: f32 = 0.85;
 pub const ARENA_LAYOUT_UI_HEIGHT_PERCENT: f32 = 0.1;
 pub const ARENA_LAYOUT_BATTLE_ARENA_ENEMY_SPAWN_HEIGHT_PERCENT: f32 = 0.2;
 pub const ARENA_LAYOUT_SIDE_GAP_WIDTH_PERCENT: f32 = 0.05;
+pub const ARENA_BASE_HEALTH: f32 = 100.;
 
 #[derive(Component, Debug, Default)]
 pub struct Tag;
 
+/// The ground doubles as the base the player defends: enemies reaching it
+/// damage its health.
 #[derive(Bundle, Default)]
 pub struct ArenaGroundBundle {
     sprite: SpriteBundle,
+    health: Health,
     tag: Tag,
 }
 impl ArenaGroundBundle {
@@ -46,6 +62,7 @@ impl ArenaGroundBundle {
                 },
                 ..default()
             },
+            health: Health::new(ARENA_BASE_HEALTH),
             ..default()
         }
     }
@@ -88,12 +105,20 @@ impl Plugin for ArenaPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(PlayerPlugin)
             .add_plugins(ProjectilePlugin)
+            .add_plugins(BeamPlugin)
+            .add_plugins(DamageNumberPlugin)
+            .add_plugins(PickupPlugin)
             .add_plugins(EnemyPlugin)
+            .add_plugins(TurretPlugin)
             .add_plugins(ArenaUIPlugin)
             .add_systems(
                 OnEnter(GameState::Encounter),
                 spawn_arena.in_set(EncounterSetupSystemSet::PrepareArena),
             )
+            .add_systems(
+                Update,
+                game_over_criteria_handler.in_set(GameSystemSet::EncounterPausable),
+            )
             .add_systems(OnExit(GameState::Encounter), despawn_arena);
     }
 }
@@ -109,3 +134,19 @@ fn despawn_arena(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
     };
     cmd.entity(encounter).despawn_recursive();
 }
+
+fn game_over_criteria_handler(
+    mut evw_transition: EventWriter<GameStateTransitionEvent>,
+    ground_query: Query<&Health, With<Tag>>,
+    players_query: Query<&player::Lives, With<player::Tag>>,
+) {
+    if ground_query.iter().any(|health| health.dead()) {
+        evw_transition.send(GameStateTransitionEvent::GameOver(
+            GameOverReason::BaseDestroyed,
+        ));
+    } else if !players_query.is_empty() && players_query.iter().all(|lives| lives.out()) {
+        evw_transition.send(GameStateTransitionEvent::GameOver(
+            GameOverReason::OutOfLives,
+        ));
+    }
+}
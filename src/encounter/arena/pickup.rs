@@ -0,0 +1,397 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::PrimaryWindow;
+use bevy_prng::WyRand;
+use bevy_rand::resource::GlobalEntropy;
+use rand::Rng;
+
+use super::enemy::EnemyDeathEvent;
+use crate::encounter::component::weapon::attachment::WeaponAttachment;
+use crate::encounter::component::weapon::attachment::WeaponStat;
+use crate::encounter::component::weapon::attachment::WeaponStatModifier;
+use crate::encounter::component::weapon::beam::BeamWeapon;
+use crate::encounter::component::weapon::secondary::SecondaryWeapon;
+use crate::encounter::component::weapon::Weapon;
+use crate::encounter::component::*;
+use crate::state::GameState;
+use crate::GameSystemSet;
+
+pub const PICKUP_ENTITY_WIDTH: f32 = 16.;
+pub const PICKUP_ENTITY_HEIGHT: f32 = 16.;
+const PICKUP_FALL_SPEED: f32 = 100.;
+const PICKUP_LIFETIME_SECS: f32 = 10.;
+const PICKUP_DROP_CHANCE: f32 = 0.15;
+const PICKUP_HEALTH_AMOUNT: f32 = 25.;
+const PICKUP_SECONDARY_CHARGES: usize = 1;
+const POWER_UP_DURATION_SECS: f32 = 8.;
+/// Attachments a weapon holds at once, the oldest one is dropped for a new
+/// one.
+const MAX_WEAPON_ATTACHMENTS: usize = 3;
+
+/// Weapons the player may get from the weapon swap pickup, besides the beam.
+const WEAPON_SWAP_POOL: [fn() -> Weapon; 7] = [
+    Weapon::shotgun,
+    Weapon::rifle,
+    Weapon::machine_gun,
+    Weapon::autocannon,
+    Weapon::cannon,
+    Weapon::rocket,
+    Weapon::mortar,
+];
+
+#[derive(Component, Debug, Default)]
+pub struct Tag;
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct PickupSettings {
+    pub drop_chance: f32,
+}
+
+impl Default for PickupSettings {
+    fn default() -> Self {
+        Self {
+            drop_chance: PICKUP_DROP_CHANCE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PowerUpKind {
+    RapidFire,
+    DoubleDamage,
+}
+
+impl PowerUpKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::RapidFire => "RAPID FIRE",
+            Self::DoubleDamage => "DOUBLE DAMAGE",
+        }
+    }
+
+    fn weapon_boosts(&self) -> &'static [WeaponStatModifier] {
+        match self {
+            Self::RapidFire => &[
+                WeaponStatModifier::Multiply(WeaponStat::FireRate, 2.),
+                WeaponStatModifier::Multiply(WeaponStat::ReloadRate, 2.),
+            ],
+            Self::DoubleDamage => &[WeaponStatModifier::Multiply(WeaponStat::Damage, 2.)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum PickupKind {
+    Ammo,
+    WeaponSwap,
+    Health,
+    SecondaryCharge,
+    PowerUp(PowerUpKind),
+    /// Random attachment for the weapon, the beam takes none.
+    Attachment,
+}
+
+impl PickupKind {
+    const ALL: [PickupKind; 7] = [
+        Self::Ammo,
+        Self::WeaponSwap,
+        Self::Health,
+        Self::SecondaryCharge,
+        Self::PowerUp(PowerUpKind::RapidFire),
+        Self::PowerUp(PowerUpKind::DoubleDamage),
+        Self::Attachment,
+    ];
+
+    fn color(&self) -> Color {
+        match self {
+            Self::Ammo => Color::GOLD,
+            Self::WeaponSwap => Color::PURPLE,
+            Self::Health => Color::GREEN,
+            Self::SecondaryCharge => Color::TEAL,
+            Self::PowerUp(PowerUpKind::RapidFire) => Color::ORANGE_RED,
+            Self::PowerUp(PowerUpKind::DoubleDamage) => Color::FUCHSIA,
+            Self::Attachment => Color::SILVER,
+        }
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Pickup {
+    kind: PickupKind,
+    lifetime: Timer,
+}
+
+impl Default for Pickup {
+    fn default() -> Self {
+        Self {
+            kind: PickupKind::Ammo,
+            lifetime: Timer::from_seconds(PICKUP_LIFETIME_SECS, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub struct ActivePowerUp {
+    pub kind: PowerUpKind,
+    pub timer: Timer,
+}
+
+/// Timed power-ups currently active on the player.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct PowerUps {
+    pub active: Vec<ActivePowerUp>,
+}
+
+impl PowerUps {
+    pub fn activate(&mut self, kind: PowerUpKind) {
+        let timer = Timer::from_seconds(POWER_UP_DURATION_SECS, TimerMode::Once);
+        match self
+            .active
+            .iter_mut()
+            .find(|power_up| power_up.kind == kind)
+        {
+            Some(power_up) => power_up.timer = timer,
+            None => self.active.push(ActivePowerUp { kind, timer }),
+        }
+    }
+
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.active.iter().any(|power_up| power_up.kind == kind)
+    }
+
+    fn weapon_boosts(&self) -> Vec<WeaponStatModifier> {
+        self.active
+            .iter()
+            .flat_map(|power_up| power_up.kind.weapon_boosts().iter().copied())
+            .collect()
+    }
+}
+
+#[derive(Bundle, Default)]
+pub struct PickupBundle {
+    pickup: Pickup,
+    size: EntitySize,
+    movable_y: MovableY,
+    spatial: SpatialBundle,
+    tag: Tag,
+    name: Name,
+}
+
+impl PickupBundle {
+    pub fn new(kind: PickupKind, translation: Vec3, window: &Window) -> Self {
+        let bottom_bound = super::battle_arena_bottom_bound(window) + PICKUP_ENTITY_HEIGHT / 2.;
+        Self {
+            pickup: Pickup { kind, ..default() },
+            size: (PICKUP_ENTITY_WIDTH, PICKUP_ENTITY_HEIGHT).into(),
+            movable_y: MovableY {
+                bound: (bottom_bound, translation.y).into(),
+                speed: PICKUP_FALL_SPEED.into(),
+            },
+            spatial: SpatialBundle::from_transform(Transform::from_translation(translation)),
+            name: Name::new(format!("Pickup {:?}", kind)),
+            ..default()
+        }
+    }
+
+    pub fn sprite_bundle(&self) -> SpriteBundle {
+        SpriteBundle {
+            sprite: Sprite {
+                color: self.pickup.kind.color(),
+                custom_size: Some(self.size.vec),
+                anchor: Anchor::Center,
+                ..default()
+            },
+            ..default()
+        }
+    }
+}
+
+pub struct PickupPlugin;
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickupSettings>()
+            .register_type::<PickupSettings>()
+            .register_type::<Pickup>()
+            .register_type::<PowerUps>()
+            .add_systems(
+                Update,
+                (drop_pickups, move_pickups, collect_pickups, tick_power_ups)
+                    .chain()
+                    .in_set(GameSystemSet::EncounterPausable),
+            )
+            .add_systems(OnExit(GameState::Encounter), despawn_pickups);
+    }
+}
+
+fn drop_pickups(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cmd: Commands,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    settings: Res<PickupSettings>,
+    mut evr_enemy_death: EventReader<EnemyDeathEvent>,
+) {
+    let window = windows.get_single().expect("Expected primary window");
+    for ev in evr_enemy_death.read() {
+        if !rng.gen_bool(settings.drop_chance.clamp(0., 1.) as f64) {
+            continue;
+        }
+        let kind = PickupKind::ALL[rng.gen_range(0..PickupKind::ALL.len())];
+        let pickup = PickupBundle::new(kind, ev.translation, window);
+        let pickup_sprite = pickup.sprite_bundle();
+        cmd.spawn(pickup).with_children(|root| {
+            root.spawn(pickup_sprite);
+        });
+    }
+}
+
+fn move_pickups(
+    timer: Res<Time>,
+    mut cmd: Commands,
+    mut query: Query<(Entity, &mut Pickup, &MovableY, &mut Transform), With<Tag>>,
+) {
+    for (pickup_entity, mut pickup, pickup_movable, mut pickup_transform) in query.iter_mut() {
+        pickup.lifetime.tick(timer.delta());
+        if pickup.lifetime.finished() {
+            cmd.entity(pickup_entity).despawn_recursive();
+            continue;
+        }
+        pickup_movable.move_down(&mut pickup_transform, &timer);
+    }
+}
+
+fn collect_pickups(
+    mut cmd: Commands,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &EntitySize,
+            &mut PowerUps,
+            Option<&mut Weapon>,
+            Option<&mut BeamWeapon>,
+            Option<&mut SecondaryWeapon>,
+            Option<&mut Health>,
+        ),
+        (
+            With<super::player::Tag>,
+            Without<super::player::Down>,
+            Without<Tag>,
+        ),
+    >,
+    pickups_query: Query<(Entity, &Pickup, &Transform, &EntitySize), With<Tag>>,
+) {
+    let mut collected = vec![];
+    for (
+        player_entity,
+        player_transform,
+        player_size,
+        mut power_ups,
+        mut weapon,
+        mut beam_weapon,
+        mut secondary_weapon,
+        mut health,
+    ) in player_query.iter_mut()
+    {
+        for (pickup_entity, pickup, pickup_transform, pickup_size) in pickups_query.iter() {
+            // the first player to reach the pickup takes it
+            if collected.contains(&pickup_entity) {
+                continue;
+            }
+            let distance = (player_transform.translation - pickup_transform.translation)
+                .truncate()
+                .abs();
+            let reach = (player_size.vec + pickup_size.vec) / 2.;
+            if distance.x > reach.x || distance.y > reach.y {
+                continue;
+            }
+            match pickup.kind {
+                PickupKind::Ammo => {
+                    if let Some(weapon) = weapon.as_mut() {
+                        weapon.refill();
+                    }
+                    if let Some(beam_weapon) = beam_weapon.as_mut() {
+                        beam_weapon.heat.cool_down();
+                    }
+                }
+                PickupKind::WeaponSwap => {
+                    // the beam is the extra option after the pool
+                    let n = rng.gen_range(0..=WEAPON_SWAP_POOL.len());
+                    let mut player = cmd.entity(player_entity);
+                    match WEAPON_SWAP_POOL.get(n) {
+                        Some(new_weapon) => {
+                            let mut new_weapon = new_weapon();
+                            // attachments move over to the new weapon
+                            for &attachment in weapon.iter().flat_map(|w| w.attachments()) {
+                                new_weapon.attach(attachment);
+                            }
+                            new_weapon.set_boosts(power_ups.weapon_boosts());
+                            player.remove::<BeamWeapon>().insert(new_weapon);
+                        }
+                        None => {
+                            player.remove::<Weapon>().insert(BeamWeapon::laser());
+                        }
+                    }
+                }
+                PickupKind::Health => {
+                    if let Some(health) = health.as_mut() {
+                        health.heal(PICKUP_HEALTH_AMOUNT);
+                    }
+                }
+                PickupKind::SecondaryCharge => {
+                    if let Some(secondary_weapon) = secondary_weapon.as_mut() {
+                        secondary_weapon.refill(PICKUP_SECONDARY_CHARGES);
+                    }
+                }
+                PickupKind::Attachment => {
+                    if let Some(weapon) = weapon.as_mut() {
+                        let attachment =
+                            WeaponAttachment::ALL[rng.gen_range(0..WeaponAttachment::ALL.len())];
+                        if weapon.attachments().len() >= MAX_WEAPON_ATTACHMENTS {
+                            let oldest = weapon.attachments()[0];
+                            weapon.detach(oldest);
+                        }
+                        weapon.attach(attachment);
+                    }
+                }
+                PickupKind::PowerUp(kind) => {
+                    power_ups.activate(kind);
+                    if let Some(weapon) = weapon.as_mut() {
+                        weapon.set_boosts(power_ups.weapon_boosts());
+                    }
+                }
+            }
+            cmd.entity(pickup_entity).despawn_recursive();
+            collected.push(pickup_entity);
+        }
+    }
+}
+
+fn tick_power_ups(
+    timer: Res<Time>,
+    mut player_query: Query<(&mut PowerUps, Option<&mut Weapon>), With<super::player::Tag>>,
+) {
+    for (mut power_ups, weapon) in player_query.iter_mut() {
+        let active_count = power_ups.active.len();
+        for power_up in power_ups.active.iter_mut() {
+            power_up.timer.tick(timer.delta());
+        }
+        power_ups
+            .active
+            .retain(|power_up| !power_up.timer.finished());
+        if power_ups.active.len() != active_count {
+            if let Some(mut weapon) = weapon {
+                weapon.set_boosts(power_ups.weapon_boosts());
+            }
+        }
+    }
+}
+
+fn despawn_pickups(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
+    for pickup in query.iter() {
+        cmd.entity(pickup).despawn_recursive();
+    }
+}
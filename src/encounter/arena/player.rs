@@ -1,59 +1,242 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+use super::enemy::EnemyDeathEvent;
+use super::pickup::PowerUps;
+use super::turret::Aim;
+use super::turret::TurretBundle;
 use super::EncounterSetupSystemSet;
+use crate::encounter::component::weapon::beam::BeamWeapon;
+use crate::encounter::component::weapon::secondary::SecondaryWeapon;
 use crate::encounter::component::weapon::*;
 use crate::encounter::component::*;
+use crate::global::input::InputAction;
+use crate::global::input::PlayerActions;
+use crate::global::input::PlayerMode;
+use crate::global::input::PlayerSlot;
+use crate::global::settings::ColorPalette;
+use crate::global::settings::GameSettings;
+use crate::global::settings::Loadout;
 use crate::state::GameState;
 use crate::GameSystemSet;
 
 #[derive(Component, Debug, Default)]
 pub struct Tag;
 
+/// Marks a player out of lives. They stay hidden in the arena, keeping their
+/// score, until the encounter ends.
+#[derive(Component, Debug, Default)]
+pub struct Down;
+
+/// Sprite of the player, coloured after their slot.
+#[derive(Component, Debug)]
+pub struct Body(PlayerSlot);
+
+/// Points earned by the player for the enemies they killed.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Score {
+    pub points: usize,
+}
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerMovementSettings {
+    /// Lets the player also move with W/S within the lower band of the arena.
+    pub vertical: bool,
+    /// Speed gained per second while a direction is held.
+    pub acceleration: f32,
+    /// Speed lost per second once the direction is released.
+    pub deceleration: f32,
+}
+
+impl Default for PlayerMovementSettings {
+    fn default() -> Self {
+        Self {
+            vertical: false,
+            acceleration: PLAYER_ACCELERATION,
+            deceleration: PLAYER_DECELERATION,
+        }
+    }
+}
+
+/// Lives left; the player loses one each time their health runs out.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Lives {
+    pub left: usize,
+}
+
+impl Lives {
+    pub fn new(left: usize) -> Self {
+        Self { left }
+    }
+
+    pub fn out(&self) -> bool {
+        self.left == 0
+    }
+}
+
+/// Grace period after losing a life, the player can't be hurt and blinks
+/// until it ends.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Invulnerable {
+    timer: Timer,
+}
+
+impl Default for Invulnerable {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(RESPAWN_INVULNERABILITY_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl Invulnerable {
+    fn blink_visible(&self) -> bool {
+        (self.timer.elapsed_secs() / RESPAWN_BLINK_SECS) as usize % 2 == 1
+    }
+}
+
+/// Short burst of speed along the movement direction, keeping the player
+/// invulnerable for a moment.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Dash {
+    /// Direction of the last horizontal movement, `-1.` or `1.`.
+    direction: f32,
+    active: Timer,
+    invulnerability: Timer,
+    cooldown: Timer,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        let finished = |secs: f32| {
+            let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+            timer.tick(timer.duration());
+            timer
+        };
+        Self {
+            direction: 1.,
+            active: finished(DASH_DURATION_SECS),
+            invulnerability: finished(DASH_INVULNERABILITY_SECS),
+            cooldown: finished(DASH_COOLDOWN_SECS),
+        }
+    }
+}
+
+impl Dash {
+    pub fn ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+
+    pub fn active(&self) -> bool {
+        !self.active.finished()
+    }
+
+    pub fn invulnerable(&self) -> bool {
+        !self.invulnerability.finished()
+    }
+
+    pub fn cooldown_remaining_secs(&self) -> f32 {
+        self.cooldown.remaining_secs()
+    }
+
+    fn tick(&mut self, timer: &Res<Time>) {
+        self.active.tick(timer.delta());
+        self.invulnerability.tick(timer.delta());
+        self.cooldown.tick(timer.delta());
+    }
+
+    fn start(&mut self) {
+        self.active.reset();
+        self.invulnerability.reset();
+        self.cooldown.reset();
+    }
+
+    fn distance(&self, timer: &Res<Time>) -> f32 {
+        self.direction * DASH_SPEED * timer.delta_seconds()
+    }
+}
+
 #[derive(Bundle, Default)]
 pub struct PlayerBundle {
-    movable: MovableX,
+    movable_x: MovableX,
+    movable_y: MovableY,
+    velocity: Velocity,
     size: EntitySize,
+    health: Health,
+    lives: Lives,
+    dash: Dash,
+    aim: Aim,
     spatial: SpatialBundle,
-    weapon: Weapon,
+    secondary_weapon: SecondaryWeapon,
+    power_ups: PowerUps,
+    slot: PlayerSlot,
+    score: Score,
     tag: Tag,
     name: Name,
 }
 
 const PLAYER_SPRITE_WIDTH: f32 = 40.;
 const PLAYER_SPRITE_HEIGHT: f32 = 90.;
+const PLAYER_HEALTH: f32 = 100.;
+const PLAYER_LIVES: usize = 3;
+/// Height of the band above the ground the player may move within, relative
+/// to the battle arena height.
+const PLAYER_VERTICAL_BAND_PERCENT: f32 = 0.25;
+const PLAYER_ACCELERATION: f32 = 2000.;
+const PLAYER_DECELERATION: f32 = 2500.;
+const DASH_SPEED: f32 = 1200.;
+const DASH_DURATION_SECS: f32 = 0.15;
+const DASH_INVULNERABILITY_SECS: f32 = 0.35;
+const DASH_COOLDOWN_SECS: f32 = 2.;
+const RESPAWN_INVULNERABILITY_SECS: f32 = 2.;
+const RESPAWN_BLINK_SECS: f32 = 0.1;
+const ENEMY_KILL_SCORE: usize = 10;
 
 impl PlayerBundle {
-    pub fn new(window: &Window, speed: f32) -> Self {
+    /// Players line up evenly along the ground, in the order of their slots.
+    pub fn new(window: &Window, speed: f32, slot: PlayerSlot, player_count: usize) -> Self {
         let left_bound = (window.resolution.width() * super::ARENA_LAYOUT_SIDE_GAP_WIDTH_PERCENT)
             + PLAYER_SPRITE_WIDTH / 2.;
         let right_bound = (window.resolution.width()
             * (1. - super::ARENA_LAYOUT_SIDE_GAP_WIDTH_PERCENT))
             - PLAYER_SPRITE_WIDTH / 2.;
-        let translation = (
-            window.resolution.width() / 2.,
-            (window.resolution.height() * super::ARENA_LAYOUT_GROUND_HEIGHT_PERCENT)
-                + PLAYER_SPRITE_HEIGHT / 2.,
-            1.,
-        )
-            .into();
+        let bottom_bound = (window.resolution.height() * super::ARENA_LAYOUT_GROUND_HEIGHT_PERCENT)
+            + PLAYER_SPRITE_HEIGHT / 2.;
+        let top_bound = bottom_bound
+            + window.resolution.height()
+                * super::ARENA_LAYOUT_BATTLE_ARENA_HEIGHT_PERCENT
+                * PLAYER_VERTICAL_BAND_PERCENT;
+        let x = window.resolution.width() * (slot.index() + 1) as f32 / (player_count + 1) as f32;
+        let translation = (x, bottom_bound, 1.).into();
         PlayerBundle {
-            movable: MovableX {
+            movable_x: MovableX {
                 bound: (left_bound, right_bound).into(),
                 speed: speed.into(),
             },
+            movable_y: MovableY {
+                bound: (bottom_bound, top_bound).into(),
+                speed: speed.into(),
+            },
             size: (PLAYER_SPRITE_WIDTH, PLAYER_SPRITE_HEIGHT).into(),
+            health: Health::new(PLAYER_HEALTH),
+            lives: Lives::new(PLAYER_LIVES),
             spatial: SpatialBundle::from_transform(Transform::from_translation(translation)),
-            weapon: Weapon::shotgun(),
-            name: Name::new("Player"),
+            secondary_weapon: SecondaryWeapon::grenade(),
+            slot,
+            name: Name::new(format!("Player {}", slot.index() + 1)),
             ..default()
         }
     }
 
-    pub fn sprite_bundle(&self) -> SpriteBundle {
+    pub fn sprite_bundle(&self, palette: ColorPalette) -> SpriteBundle {
         SpriteBundle {
             sprite: Sprite {
-                color: Color::MAROON,
+                color: palette.player_color(self.slot),
                 custom_size: Some(self.size.vec),
                 anchor: bevy::sprite::Anchor::Center,
                 ..default()
@@ -66,37 +249,222 @@ impl PlayerBundle {
 pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(GameState::Encounter),
-            spawn_player_bundle.in_set(EncounterSetupSystemSet::SpawnEntities),
-        )
-        .add_systems(
-            Update,
-            movement_controls_handler.in_set(GameSystemSet::EncounterPausable),
-        );
+        app.init_resource::<PlayerMovementSettings>()
+            .register_type::<PlayerMovementSettings>()
+            .register_type::<Lives>()
+            .register_type::<Dash>()
+            .register_type::<Invulnerable>()
+            .register_type::<Score>()
+            .register_type::<PlayerSlot>()
+            .add_systems(
+                OnEnter(GameState::Encounter),
+                spawn_player_bundle.in_set(EncounterSetupSystemSet::SpawnEntities),
+            )
+            .add_systems(OnExit(GameState::Encounter), despawn_players)
+            .add_systems(
+                Update,
+                (
+                    dash_controls_handler,
+                    movement_controls_handler,
+                    invulnerability_handler,
+                    player_death_handler,
+                )
+                    .chain()
+                    .in_set(GameSystemSet::EncounterPausable),
+            )
+            .add_systems(Update, score_kills.in_set(GameSystemSet::EncounterPausable))
+            .add_systems(Update, apply_palette.in_set(GameSystemSet::Encounter))
+            .add_systems(Update, apply_settings.in_set(GameSystemSet::Global));
     }
 }
 
 const PLAYER_SPEED: f32 = 250.;
-fn spawn_player_bundle(windows: Query<&Window, With<PrimaryWindow>>, mut cmd: Commands) {
+fn spawn_player_bundle(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    player_mode: Res<PlayerMode>,
+    settings: Res<GameSettings>,
+    mut cmd: Commands,
+) {
     let window = windows.get_single().expect("Expected primary window");
-    let player_bundle = PlayerBundle::new(window, PLAYER_SPEED);
-    let sprite_bundle = player_bundle.sprite_bundle();
-    cmd.spawn(player_bundle).with_children(|root| {
-        root.spawn(sprite_bundle);
-    });
+    let slots = player_mode.slots();
+    for &slot in slots {
+        let player_bundle = PlayerBundle::new(window, PLAYER_SPEED, slot, slots.len());
+        let sprite_bundle = player_bundle.sprite_bundle(settings.palette);
+        let mut player = cmd.spawn(player_bundle);
+        match loadout_weapon(settings.loadout) {
+            Some(weapon) => player.insert(weapon),
+            None => player.insert(BeamWeapon::laser()),
+        };
+        player.with_children(|root| {
+            root.spawn(sprite_bundle).insert(Body(slot));
+            root.spawn(TurretBundle::new());
+        });
+    }
+}
+
+/// Projectile weapon of the loadout, `None` for the beam.
+fn loadout_weapon(loadout: Loadout) -> Option<Weapon> {
+    match loadout {
+        Loadout::Shotgun => Some(Weapon::shotgun()),
+        Loadout::Rifle => Some(Weapon::rifle()),
+        Loadout::MachineGun => Some(Weapon::machine_gun()),
+        Loadout::Autocannon => Some(Weapon::autocannon()),
+        Loadout::Cannon => Some(Weapon::cannon()),
+        Loadout::Rocket => Some(Weapon::rocket()),
+        Loadout::Mortar => Some(Weapon::mortar()),
+        Loadout::Laser => None,
+    }
+}
+
+fn despawn_players(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
+    for player in query.iter() {
+        cmd.entity(player).despawn_recursive();
+    }
+}
+
+fn apply_settings(game_settings: Res<GameSettings>, mut settings: ResMut<PlayerMovementSettings>) {
+    if game_settings.is_changed() {
+        settings.vertical = game_settings.vertical_movement;
+    }
+}
+
+/// Recolours the players when the palette is changed from the pause menu.
+fn apply_palette(settings: Res<GameSettings>, mut query: Query<(&Body, &mut Sprite)>) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (body, mut sprite) in query.iter_mut() {
+        sprite.color = settings.palette.player_color(body.0);
+    }
+}
+
+fn dash_controls_handler(
+    timer: Res<Time>,
+    player_actions: Res<PlayerActions>,
+    mut query: Query<(&PlayerSlot, &mut Dash, &Velocity), (With<Tag>, Without<Down>)>,
+) {
+    for (player_slot, mut player_dash, player_velocity) in query.iter_mut() {
+        player_dash.tick(&timer);
+        if player_velocity.linear.x != 0. {
+            player_dash.direction = player_velocity.linear.x.signum();
+        }
+        let actions = player_actions.buttons(*player_slot);
+        if actions.just_pressed(InputAction::Dash) && player_dash.ready() {
+            player_dash.start();
+        }
+    }
 }
 
 fn movement_controls_handler(
     timer: Res<Time>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&MovableX, &mut Transform), With<super::player::Tag>>,
+    player_actions: Res<PlayerActions>,
+    settings: Res<PlayerMovementSettings>,
+    mut query: Query<
+        (
+            &PlayerSlot,
+            &MovableX,
+            &MovableY,
+            &Dash,
+            &mut Velocity,
+            &mut Transform,
+        ),
+        (With<Tag>, Without<Down>),
+    >,
+) {
+    for (
+        player_slot,
+        player_movable_x,
+        player_movable_y,
+        player_dash,
+        mut player_velocity,
+        mut player_transform,
+    ) in query.iter_mut()
+    {
+        if player_dash.active() {
+            // the dash overrides the controls, the momentum is kept for after it
+            player_movable_x.shift(&mut player_transform, player_dash.distance(&timer));
+            continue;
+        }
+        let direction = Vec2::new(
+            player_actions.axis(*player_slot, InputAction::MoveLeft, InputAction::MoveRight),
+            if settings.vertical {
+                player_actions.axis(*player_slot, InputAction::MoveDown, InputAction::MoveUp)
+            } else {
+                0.
+            },
+        );
+        let target = Vec2::new(
+            direction.x * f32::from(&player_movable_x.speed),
+            direction.y * f32::from(&player_movable_y.speed),
+        );
+        let velocity = &mut player_velocity.linear;
+        for (actual, target, direction) in [
+            (&mut velocity.x, target.x, direction.x),
+            (&mut velocity.y, target.y, direction.y),
+        ] {
+            let rate = if direction != 0. {
+                settings.acceleration
+            } else {
+                settings.deceleration
+            };
+            let step = rate * timer.delta_seconds();
+            *actual += (target - *actual).clamp(-step, step);
+        }
+        if !player_movable_x.shift(&mut player_transform, velocity.x * timer.delta_seconds()) {
+            velocity.x = 0.;
+        }
+        if !player_movable_y.shift(&mut player_transform, velocity.y * timer.delta_seconds()) {
+            velocity.y = 0.;
+        }
+    }
+}
+
+fn player_death_handler(
+    mut cmd: Commands,
+    mut query: Query<(Entity, &mut Health, &mut Lives), (With<Tag>, Without<Down>)>,
+) {
+    for (player_entity, mut player_health, mut player_lives) in query.iter_mut() {
+        if !player_health.dead() {
+            continue;
+        }
+        player_lives.left = player_lives.left.saturating_sub(1);
+        if player_lives.out() {
+            cmd.entity(player_entity).insert((Down, Visibility::Hidden));
+        } else {
+            player_health.actual = player_health.max;
+            cmd.entity(player_entity).insert(Invulnerable::default());
+        }
+    }
+}
+
+fn invulnerability_handler(
+    mut cmd: Commands,
+    timer: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility), (With<Tag>, Without<Down>)>,
 ) {
-    let (player_movable, mut player_transform) = query.get_single_mut().expect("Expected player");
-    if keyboard_input.pressed(KeyCode::KeyA) {
-        player_movable.move_left(player_transform.as_mut(), &timer);
+    for (player_entity, mut invulnerable, mut player_visibility) in query.iter_mut() {
+        invulnerable.timer.tick(timer.delta());
+        if invulnerable.timer.finished() {
+            cmd.entity(player_entity).remove::<Invulnerable>();
+            *player_visibility = Visibility::Inherited;
+        } else if invulnerable.blink_visible() {
+            *player_visibility = Visibility::Inherited;
+        } else {
+            *player_visibility = Visibility::Hidden;
+        }
     }
-    if keyboard_input.pressed(KeyCode::KeyD) {
-        player_movable.move_right(player_transform.as_mut(), &timer);
+}
+
+fn score_kills(
+    mut evr_enemy_death: EventReader<EnemyDeathEvent>,
+    mut query: Query<&mut Score, With<Tag>>,
+) {
+    for ev in evr_enemy_death.read() {
+        let Some(killer) = ev.killer else {
+            continue;
+        };
+        if let Ok(mut score) = query.get_mut(killer) {
+            score.points += ENEMY_KILL_SCORE;
+        }
     }
 }
@@ -1,9 +1,18 @@
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
 use bevy_prng::WyRand;
 use bevy_rand::resource::GlobalEntropy;
 
+use super::enemy::EnemyDeathEvent;
+use super::turret::Aim;
+use crate::encounter::component::weapon::secondary::SecondaryWeapon;
 use crate::encounter::component::weapon::*;
 use crate::encounter::component::*;
+use crate::global::input::InputAction;
+use crate::global::input::PlayerActions;
+use crate::global::input::PlayerSlot;
+use crate::state::GameState;
 use crate::GameSystemSet;
 
 #[derive(Component, Debug, Default)]
@@ -12,15 +21,25 @@ pub struct Tag;
 #[derive(Bundle, Default)]
 pub struct ProjectileBundle {
     velocity: Velocity,
+    ballistics: Ballistics,
+    detonation: Detonation,
+    ricochet: Ricochet,
+    damage: ShotDamage,
+    damage_multiplier: DamageMultiplier,
     spatial: SpatialBundle,
     tag: Tag,
     name: Name,
 }
 
 impl ProjectileBundle {
-    pub fn new(velocity: Velocity, transform: Transform) -> Self {
+    pub fn new(velocity: Velocity, transform: Transform, weapon_shots: &WeaponShots) -> Self {
         Self {
             velocity,
+            ballistics: weapon_shots.ballistics.clone(),
+            detonation: weapon_shots.detonation.clone(),
+            ricochet: weapon_shots.ricochet.clone(),
+            damage: weapon_shots.damage.clone(),
+            damage_multiplier: weapon_shots.damage_multiplier.into(),
             spatial: SpatialBundle::from_transform(transform),
             name: Name::new("Shot"),
             ..default()
@@ -37,96 +56,236 @@ impl Plugin for ProjectilePlugin {
         )
         .add_systems(
             Update,
-            fire_controls_handler.in_set(GameSystemSet::EncounterPausable),
+            (fire_controls_handler, secondary_fire_controls_handler)
+                .in_set(GameSystemSet::EncounterPausable),
         )
         .add_systems(
             Update,
-            (hit_controls_handler, despawn_enemies, redraw_health_sprites)
+            (hit_controls_handler, kill_enemies, redraw_health_sprites)
                 .chain()
                 .in_set(GameSystemSet::EncounterPausable),
-        );
+        )
+        .add_systems(OnExit(GameState::Encounter), despawn_projectiles)
+        .add_event::<HitEvent>();
     }
 }
 
 fn move_projectile(
     timer: Res<Time>,
+    windows: Query<&Window, With<PrimaryWindow>>,
     mut cmd: Commands,
-    mut projectiles_query: Query<(Entity, &Velocity, &mut Transform), With<Tag>>,
+    mut projectiles_query: Query<
+        (
+            Entity,
+            &mut Velocity,
+            &Ballistics,
+            &mut Detonation,
+            &mut Ricochet,
+            &mut DamageMultiplier,
+            &mut Transform,
+        ),
+        With<Tag>,
+    >,
 ) {
-    for (projectile_entity, projectile_velocity, mut projectile_transform) in &mut projectiles_query
+    let window = windows.get_single().expect("Expected primary window");
+    let walls: MovementBound = (
+        super::battle_arena_left_bound(window),
+        super::battle_arena_right_bound(window),
+    )
+        .into();
+    for (
+        projectile_entity,
+        mut projectile_velocity,
+        projectile_ballistics,
+        mut projectile_detonation,
+        mut projectile_ricochet,
+        mut projectile_damage_multiplier,
+        mut projectile_transform,
+    ) in &mut projectiles_query
     {
-        if projectile_transform.translation.x > 2000. {
+        projectile_ricochet.try_bounce(
+            &walls,
+            &mut projectile_transform,
+            &mut projectile_velocity,
+            &mut projectile_damage_multiplier,
+        );
+        let translation = projectile_transform.translation;
+        if translation.x < 0.
+            || translation.x > window.resolution.width()
+            || translation.y < 0.
+            || translation.y > window.resolution.height()
+        {
             cmd.entity(projectile_entity).despawn_recursive();
-            return;
+            continue;
         }
+        projectile_ballistics.apply(&timer, &mut projectile_velocity);
         projectile_velocity.advance(&timer, &mut projectile_transform);
+        projectile_detonation.tick(&timer);
     }
 }
 
 fn fire_controls_handler(
     mut cmd: Commands,
-    rng: ResMut<GlobalEntropy<WyRand>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
     global_timer: Res<Time>,
-    mut player_query: Query<(&Transform, &mut Weapon), With<crate::encounter::arena::player::Tag>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &PlayerSlot,
+            &Transform,
+            &Aim,
+            &mut Weapon,
+            &mut Velocity,
+        ),
+        (
+            With<crate::encounter::arena::player::Tag>,
+            Without<crate::encounter::arena::player::Down>,
+        ),
+    >,
+    player_actions: Res<PlayerActions>,
 ) {
-    let (player_transform, mut player_weapon) =
-        player_query.get_single_mut().expect("Expected player");
-    player_weapon.tick(global_timer);
-    if keyboard_input.pressed(KeyCode::Space) {
-        match player_weapon.shoot(player_transform.translation, rng) {
-            Some((shots, shot_sprite)) => {
-                for (velocity, transform) in shots.into_iter() {
-                    let projectile = ProjectileBundle::new(velocity, transform);
-                    cmd.spawn(projectile).with_children(|root| {
-                        root.spawn(shot_sprite.clone());
-                    });
-                }
-            }
-            None => (),
+    for (
+        player_entity,
+        player_slot,
+        player_transform,
+        player_aim,
+        mut player_weapon,
+        mut player_velocity,
+    ) in player_query.iter_mut()
+    {
+        player_weapon.tick(&global_timer);
+        let trigger = WeaponTrigger::new(player_actions.buttons(*player_slot), InputAction::Fire);
+        if let Some(weapon_shots) = player_weapon.shoot(
+            trigger,
+            player_aim.shot_transform(player_transform.translation),
+            &mut rng,
+        ) {
+            player_velocity.linear += weapon_shots.recoil;
+            spawn_projectiles(&mut cmd, weapon_shots, player_entity);
+        }
+    }
+}
+
+fn secondary_fire_controls_handler(
+    mut cmd: Commands,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    global_timer: Res<Time>,
+    mut player_query: Query<
+        (Entity, &PlayerSlot, &Transform, &Aim, &mut SecondaryWeapon),
+        (
+            With<crate::encounter::arena::player::Tag>,
+            Without<crate::encounter::arena::player::Down>,
+        ),
+    >,
+    player_actions: Res<PlayerActions>,
+) {
+    for (player_entity, player_slot, player_transform, player_aim, mut secondary_weapon) in
+        player_query.iter_mut()
+    {
+        secondary_weapon.tick(&global_timer);
+        let trigger = WeaponTrigger::new(
+            player_actions.buttons(*player_slot),
+            InputAction::SecondaryFire,
+        );
+        if let Some(weapon_shots) = secondary_weapon.shoot(
+            trigger,
+            player_aim.shot_transform(player_transform.translation),
+            &mut rng,
+        ) {
+            spawn_projectiles(&mut cmd, weapon_shots, player_entity);
         }
     }
 }
 
+fn spawn_projectiles(cmd: &mut Commands, mut weapon_shots: WeaponShots, shooter: Entity) {
+    for (velocity, transform) in std::mem::take(&mut weapon_shots.shots) {
+        let projectile = ProjectileBundle::new(velocity, transform, &weapon_shots);
+        cmd.spawn(projectile)
+            .insert(Shooter(shooter))
+            .with_children(|root| {
+                root.spawn(weapon_shots.sprite.clone());
+            });
+    }
+}
+
 fn hit_controls_handler(
     mut cmd: Commands,
-    weapon_query: Query<&Weapon, With<crate::encounter::arena::player::Tag>>,
-    mut projectiles_query: Query<(Entity, &Transform), With<super::projectile::Tag>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    mut evw_hit: EventWriter<HitEvent>,
+    mut projectiles_query: Query<
+        (
+            Entity,
+            &Transform,
+            &Velocity,
+            &Detonation,
+            &ShotDamage,
+            &DamageMultiplier,
+            &Shooter,
+        ),
+        With<super::projectile::Tag>,
+    >,
     enemies_query: Query<&Transform, With<super::enemy::Tag>>,
     mut health_query: Query<(&mut Health, &Parent), With<super::health::Tag>>,
 ) {
-    let player_weapon = weapon_query.get_single().expect("Expected player");
-
     let mut enemies = vec![];
     for (health, parent) in health_query.iter_mut() {
         let enemy_transform = enemies_query
             .get(parent.get())
             .expect("Health component without parent enemy");
-        enemies.push((health, enemy_transform));
+        enemies.push((parent.get(), health, enemy_transform));
     }
 
-    for (projectile_entity, projectile_transform) in &mut projectiles_query {
-        match player_weapon.try_apply_damage(projectile_transform, &mut enemies) {
-            Some(applied_damage) => {
-                bevy::log::info!("Applied damage: {}", applied_damage);
+    for (
+        projectile_entity,
+        projectile_transform,
+        projectile_velocity,
+        projectile_detonation,
+        shot_damage,
+        damage_multiplier,
+        shooter,
+    ) in &mut projectiles_query
+    {
+        let detonated = projectile_detonation.triggered(projectile_transform, projectile_velocity);
+        match shot_damage.try_apply_damage(
+            projectile_transform,
+            shooter.0,
+            damage_multiplier.into(),
+            detonated,
+            &mut enemies,
+            &mut rng,
+        ) {
+            Some(hits) => {
+                evw_hit.send_batch(hits);
                 cmd.entity(projectile_entity).despawn_recursive()
             }
+            None if detonated => cmd.entity(projectile_entity).despawn_recursive(),
             None => (),
         }
     }
 }
 
-fn despawn_enemies(
+fn kill_enemies(
     mut cmd: Commands,
-    enemies_query: Query<Entity, With<super::enemy::Tag>>,
+    mut evr_hit: EventReader<HitEvent>,
+    mut evw_enemy_death: EventWriter<EnemyDeathEvent>,
+    enemies_query: Query<(Entity, &Transform, Has<super::enemy::Dead>), With<super::enemy::Tag>>,
     mut health_query: Query<(&mut Health, &Parent), With<super::health::Tag>>,
 ) {
+    // the last hit on a dead enemy is the killing one
+    let last_shooters: HashMap<Entity, Entity> = evr_hit
+        .read()
+        .map(|hit| (hit.target, hit.shooter))
+        .collect();
     for (health, parent) in health_query.iter_mut() {
-        let enemy_entity = enemies_query
+        let (enemy_entity, enemy_transform, enemy_dead) = enemies_query
             .get(parent.get())
             .expect("Health component without parent enemy");
-        if health.dead() {
-            cmd.entity(enemy_entity).despawn_recursive();
+        if health.dead() && !enemy_dead {
+            evw_enemy_death.send(EnemyDeathEvent {
+                translation: enemy_transform.translation,
+                killer: last_shooters.get(&enemy_entity).copied(),
+            });
+            cmd.entity(enemy_entity).insert(super::enemy::Dead);
         }
     }
 }
@@ -145,3 +304,9 @@ fn redraw_health_sprites(
         }
     }
 }
+
+fn despawn_projectiles(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
+    for projectile in query.iter() {
+        cmd.entity(projectile).despawn_recursive();
+    }
+}
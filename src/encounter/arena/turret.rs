@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::PrimaryWindow;
+
+use crate::global::input::PlayerSlot;
+use crate::global::main_camera;
+use crate::global::settings::GameSettings;
+use crate::GameSystemSet;
+
+const TURRET_SPRITE_WIDTH: f32 = 10.;
+const TURRET_SPRITE_HEIGHT: f32 = 55.;
+const TURRET_SPRITE_COLOR: Color = Color::GRAY;
+const AIM_MAX_ANGLE_DEG: f32 = 75.;
+
+#[derive(Component, Debug, Default)]
+pub struct Tag;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum AimMode {
+    /// The turret always points straight up.
+    #[default]
+    Fixed,
+    /// The turret of player one follows the mouse cursor, the other players
+    /// keep pointing straight up.
+    Mouse,
+}
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AimSettings {
+    pub mode: AimMode,
+    /// Max turret deviation from straight up, to either side.
+    pub max_angle_deg: f32,
+}
+
+impl Default for AimSettings {
+    fn default() -> Self {
+        Self {
+            mode: AimMode::Fixed,
+            max_angle_deg: AIM_MAX_ANGLE_DEG,
+        }
+    }
+}
+
+/// Direction the turret points at, as a counterclockwise angle from straight
+/// up.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Aim {
+    pub angle_rad: f32,
+}
+
+impl Aim {
+    pub fn rotation(&self) -> Quat {
+        Quat::from_rotation_z(self.angle_rad)
+    }
+
+    /// Transform the shots leave the turret with.
+    pub fn shot_transform(&self, translation: Vec3) -> Transform {
+        Transform::from_translation(translation).with_rotation(self.rotation())
+    }
+}
+
+#[derive(Bundle, Default)]
+pub struct TurretBundle {
+    sprite: SpriteBundle,
+    tag: Tag,
+    name: Name,
+}
+
+impl TurretBundle {
+    pub fn new() -> Self {
+        Self {
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    color: TURRET_SPRITE_COLOR,
+                    custom_size: Some(Vec2::new(TURRET_SPRITE_WIDTH, TURRET_SPRITE_HEIGHT)),
+                    anchor: Anchor::BottomCenter,
+                    ..default()
+                },
+                // above the player sprite
+                transform: Transform::from_xyz(0., 0., 0.1),
+                ..default()
+            },
+            name: Name::new("Turret"),
+            ..default()
+        }
+    }
+}
+
+pub struct TurretPlugin;
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AimSettings>()
+            .register_type::<AimSettings>()
+            .register_type::<Aim>()
+            .add_systems(
+                Update,
+                (aim_controls_handler, redraw_turrets)
+                    .chain()
+                    .in_set(GameSystemSet::EncounterPausable),
+            )
+            .add_systems(Update, apply_settings.in_set(GameSystemSet::Global));
+    }
+}
+
+fn apply_settings(game_settings: Res<GameSettings>, mut settings: ResMut<AimSettings>) {
+    if game_settings.is_changed() {
+        settings.mode = if game_settings.mouse_aim {
+            AimMode::Mouse
+        } else {
+            AimMode::Fixed
+        };
+    }
+}
+
+fn aim_controls_handler(
+    settings: Res<AimSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<main_camera::Tag>>,
+    mut players_query: Query<(&PlayerSlot, &Transform, &mut Aim), With<super::player::Tag>>,
+) {
+    let max_angle_rad = settings.max_angle_deg.max(0.).to_radians();
+    let target = match settings.mode {
+        AimMode::Fixed => None,
+        AimMode::Mouse => {
+            let window = windows.get_single().expect("Expected primary window");
+            let (camera, camera_transform) =
+                camera_query.get_single().expect("Expected main camera");
+            window
+                .cursor_position()
+                .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+        }
+    };
+    for (player_slot, player_transform, mut player_aim) in players_query.iter_mut() {
+        let angle_rad = match (settings.mode, target) {
+            (AimMode::Fixed, _) => 0.,
+            // the mouse belongs to the keyboard player
+            (AimMode::Mouse, _) if *player_slot != PlayerSlot::One => 0.,
+            (AimMode::Mouse, Some(target)) => {
+                let direction = target - player_transform.translation.truncate();
+                (-direction.x).atan2(direction.y)
+            }
+            // cursor left the window, keep the last aim
+            (AimMode::Mouse, None) => player_aim.angle_rad,
+        };
+        player_aim.angle_rad = angle_rad.clamp(-max_angle_rad, max_angle_rad);
+    }
+}
+
+fn redraw_turrets(
+    players_query: Query<(&Aim, &Children), With<super::player::Tag>>,
+    mut turrets_query: Query<&mut Transform, With<Tag>>,
+) {
+    for (player_aim, player_children) in players_query.iter() {
+        for &child in player_children {
+            if let Ok(mut turret_transform) = turrets_query.get_mut(child) {
+                turret_transform.rotation = player_aim.rotation();
+            }
+        }
+    }
+}
@@ -1,9 +1,37 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+use super::pickup::PowerUps;
+use super::player::Dash;
+use super::player::Down;
+use super::player::Lives;
+use super::player::Score;
+use crate::encounter::component::weapon::beam::BeamWeapon;
+use crate::encounter::component::weapon::heat::WeaponHeat;
+use crate::encounter::component::weapon::secondary::SecondaryWeapon;
+use crate::encounter::component::weapon::Weapon;
+use crate::encounter::component::Health;
+use crate::global::input::PlayerMode;
+use crate::global::input::PlayerSlot;
+use crate::global::settings::ColorPalette;
+use crate::global::settings::GameSettings;
 use crate::state::GameState;
+use crate::GameSystemSet;
 
 const ROOT_NODE_COLOR: Color = Color::BLACK;
+const HUD_TEXT_SIZE: f32 = 16.;
+const HUD_TEXT_COLOR: Color = Color::WHITE;
+const GAUGE_WIDTH_PX: f32 = 100.;
+const GAUGE_HEIGHT_PX: f32 = 14.;
+const GAUGE_BORDER_COLOR: Color = Color::WHITE;
+const HEAT_GAUGE_TEXT: &str = "HEAT";
+const HEAT_GAUGE_FILL_COLOR: Color = Color::ORANGE;
+const HEAT_GAUGE_OVERHEATED_COLOR: Color = Color::RED;
+const POWER_UP_TEXT_COLOR: Color = Color::GOLD;
+const SECONDARY_CHARGES_TEXT: &str = "GRENADES";
+const DASH_TEXT: &str = "DASH";
+const BASE_TEXT: &str = "BASE";
+const DOWN_TEXT: &str = "DOWN";
 
 #[derive(Component, Debug)]
 pub struct Tag;
@@ -14,18 +42,63 @@ pub struct ContinueButton;
 #[derive(Component, Debug)]
 pub struct MainMenuButton;
 
+#[derive(Component, Debug)]
+pub struct BaseStatus;
+
+#[derive(Component, Debug)]
+pub struct HeatGauge(PlayerSlot);
+
+#[derive(Component, Debug)]
+pub struct HeatGaugeFill(PlayerSlot);
+
+#[derive(Component, Debug)]
+pub struct PowerUpCountdown(PlayerSlot);
+
+#[derive(Component, Debug)]
+pub struct SecondaryCharges(PlayerSlot);
+
+#[derive(Component, Debug)]
+pub struct SurvivalStatus(PlayerSlot);
+
+#[derive(Component, Debug)]
+pub struct DashCooldown(PlayerSlot);
+
 pub struct ArenaUIPlugin;
 
 impl Plugin for ArenaUIPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Encounter), spawn_arena_ui)
-            .add_systems(OnExit(GameState::Encounter), despawn_arena_ui);
+            .add_systems(OnExit(GameState::Encounter), despawn_arena_ui)
+            .add_systems(
+                Update,
+                (
+                    redraw_base_status,
+                    redraw_heat_gauge,
+                    redraw_power_up_countdown,
+                    redraw_secondary_charges,
+                    redraw_survival_status,
+                    redraw_dash_cooldown,
+                )
+                    .in_set(GameSystemSet::Encounter),
+            );
     }
 }
 
-fn spawn_arena_ui(windows: Query<&Window, With<PrimaryWindow>>, mut cmd: Commands) {
+fn spawn_arena_ui(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    player_mode: Res<PlayerMode>,
+    settings: Res<GameSettings>,
+    mut cmd: Commands,
+) {
     let window = windows.get_single().expect("Expected primary window");
+    let slots = player_mode.slots();
     cmd.spawn(root_node(window))
+        .with_children(base_status)
+        .with_children(|root| {
+            for &slot in slots {
+                player_panel(root, slot, settings.palette);
+            }
+        })
         // .with_children(ui_zone_1)
         // .with_children(ui_zone_2)
         // .with_children(ui_zone_3)
@@ -40,23 +113,162 @@ fn despawn_arena_ui(mut cmd: Commands, query: Query<Entity, With<Tag>>) {
     cmd.entity(menu).despawn_recursive();
 }
 
+fn redraw_base_status(
+    ground_query: Query<&Health, With<super::Tag>>,
+    mut status_query: Query<&mut Text, With<BaseStatus>>,
+) {
+    let (Ok(ground_health), Ok(mut status_text)) =
+        (ground_query.get_single(), status_query.get_single_mut())
+    else {
+        return;
+    };
+    status_text.sections[0].value = format!("{}: {:.0}", BASE_TEXT, ground_health.actual.max(0.));
+}
+
+fn redraw_heat_gauge(
+    player_query: Query<
+        (&PlayerSlot, Option<&Weapon>, Option<&BeamWeapon>),
+        With<crate::encounter::arena::player::Tag>,
+    >,
+    mut gauge_query: Query<(&mut Style, &HeatGauge), Without<HeatGaugeFill>>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor, &HeatGaugeFill)>,
+) {
+    for (mut gauge_style, gauge) in gauge_query.iter_mut() {
+        let heat: Option<&WeaponHeat> = match player_query
+            .iter()
+            .find(|(player_slot, _, _)| **player_slot == gauge.0)
+        {
+            Some((_, _, Some(beam_weapon))) => Some(&beam_weapon.heat),
+            Some((_, Some(weapon), None)) => weapon.heat.as_ref(),
+            _ => None,
+        };
+        let Some(heat) = heat else {
+            gauge_style.display = Display::None;
+            continue;
+        };
+        gauge_style.display = Display::Flex;
+        for (mut fill_style, mut fill_color, fill) in fill_query.iter_mut() {
+            if fill.0 != gauge.0 {
+                continue;
+            }
+            fill_style.width = Val::Percent(heat.ratio() * 100.);
+            *fill_color = if heat.overheated() {
+                HEAT_GAUGE_OVERHEATED_COLOR.into()
+            } else {
+                HEAT_GAUGE_FILL_COLOR.into()
+            };
+        }
+    }
+}
+
+fn redraw_power_up_countdown(
+    player_query: Query<(&PlayerSlot, &PowerUps), With<crate::encounter::arena::player::Tag>>,
+    mut countdown_query: Query<(&mut Text, &PowerUpCountdown)>,
+) {
+    for (player_slot, power_ups) in player_query.iter() {
+        for (mut countdown_text, countdown) in countdown_query.iter_mut() {
+            if countdown.0 != *player_slot {
+                continue;
+            }
+            countdown_text.sections = power_ups
+                .active
+                .iter()
+                .map(|power_up| TextSection {
+                    value: format!(
+                        "{}: {:.1}\n",
+                        power_up.kind.title(),
+                        power_up.timer.remaining_secs()
+                    ),
+                    style: TextStyle {
+                        font_size: HUD_TEXT_SIZE,
+                        color: POWER_UP_TEXT_COLOR,
+                        ..default()
+                    },
+                })
+                .collect();
+        }
+    }
+}
+
+fn redraw_secondary_charges(
+    player_query: Query<
+        (&PlayerSlot, &SecondaryWeapon),
+        With<crate::encounter::arena::player::Tag>,
+    >,
+    mut charges_query: Query<(&mut Text, &SecondaryCharges)>,
+) {
+    for (player_slot, secondary_weapon) in player_query.iter() {
+        for (mut charges_text, charges) in charges_query.iter_mut() {
+            if charges.0 != *player_slot {
+                continue;
+            }
+            charges_text.sections[0].value = format!(
+                "{}: {}/{}",
+                SECONDARY_CHARGES_TEXT,
+                secondary_weapon.charges(),
+                secondary_weapon.max_charges()
+            );
+        }
+    }
+}
+
+fn redraw_survival_status(
+    settings: Res<GameSettings>,
+    player_query: Query<
+        (&PlayerSlot, &Health, &Lives, &Score, Has<Down>),
+        With<crate::encounter::arena::player::Tag>,
+    >,
+    mut status_query: Query<(&mut Text, &SurvivalStatus)>,
+) {
+    for (player_slot, player_health, player_lives, player_score, down) in player_query.iter() {
+        for (mut status_text, status) in status_query.iter_mut() {
+            if status.0 != *player_slot {
+                continue;
+            }
+            let survival = if down {
+                DOWN_TEXT.to_string()
+            } else {
+                format!(
+                    "LIVES: {}  HP: {:.0}",
+                    player_lives.left,
+                    player_health.actual.max(0.)
+                )
+            };
+            // the palette can be changed from the pause menu
+            status_text.sections[0].style.color = settings.palette.player_color(status.0);
+            status_text.sections[1].value =
+                format!("  SCORE: {}\n{}", player_score.points, survival);
+        }
+    }
+}
+
+fn redraw_dash_cooldown(
+    player_query: Query<(&PlayerSlot, &Dash), With<crate::encounter::arena::player::Tag>>,
+    mut cooldown_query: Query<(&mut Text, &DashCooldown)>,
+) {
+    for (player_slot, dash) in player_query.iter() {
+        for (mut cooldown_text, cooldown) in cooldown_query.iter_mut() {
+            if cooldown.0 != *player_slot {
+                continue;
+            }
+            cooldown_text.sections[0].value = if dash.ready() {
+                format!("{}: READY", DASH_TEXT)
+            } else {
+                format!("{}: {:.1}", DASH_TEXT, dash.cooldown_remaining_secs())
+            };
+        }
+    }
+}
+
 fn root_node(window: &Window) -> NodeBundle {
     NodeBundle {
         style: Style {
             width: Val::Percent(100.0),
             height: Val::Px(super::ui_height(window)),
-            display: Display::Grid,
-            // padding: UiRect {
-            //     left: Val::Px(25.0),
-            //     right: Val::Px(25.0),
-            //     top: Val::Px(25.0),
-            //     ..default()
-            // },
-            // grid_template_columns: RepeatedGridTrack::flex(4, 1.0),
-            // grid_template_rows: RepeatedGridTrack::flex(1, 1.0),
-            // grid_auto_flow: GridAutoFlow::Row,
-            // row_gap: Val::Px(10.0),
-            // column_gap: Val::Px(10.0),
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            padding: UiRect::horizontal(Val::Px(25.0)),
+            column_gap: Val::Px(25.0),
             ..default()
         },
         background_color: ROOT_NODE_COLOR.into(),
@@ -64,6 +276,106 @@ fn root_node(window: &Window) -> NodeBundle {
     }
 }
 
+fn hud_text(value: impl Into<String>) -> TextBundle {
+    TextBundle::from_section(value, TextStyle {
+        font_size: HUD_TEXT_SIZE,
+        color: HUD_TEXT_COLOR,
+        ..default()
+    })
+}
+
+fn base_status(root: &mut ChildBuilder) {
+    root.spawn(hud_text("")).insert(BaseStatus);
+}
+
+/// Stats of a single player, next to their power-ups.
+fn player_panel(root: &mut ChildBuilder, slot: PlayerSlot, palette: ColorPalette) {
+    root.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        ..default()
+    })
+    .with_children(|panel| {
+        survival_status(panel, slot, palette);
+        panel
+            .spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| {
+                row.spawn(hud_text("")).insert(SecondaryCharges(slot));
+                row.spawn(hud_text("")).insert(DashCooldown(slot));
+            });
+        heat_gauge(panel, slot);
+    });
+    root.spawn(TextBundle::default())
+        .insert(PowerUpCountdown(slot));
+}
+
+fn survival_status(root: &mut ChildBuilder, slot: PlayerSlot, palette: ColorPalette) {
+    root.spawn(TextBundle::from_sections([
+        TextSection::new(slot.title(), TextStyle {
+            font_size: HUD_TEXT_SIZE,
+            color: palette.player_color(slot),
+            ..default()
+        }),
+        TextSection::new("", TextStyle {
+            font_size: HUD_TEXT_SIZE,
+            color: HUD_TEXT_COLOR,
+            ..default()
+        }),
+    ]))
+    .insert(SurvivalStatus(slot));
+}
+
+fn heat_gauge(root: &mut ChildBuilder, slot: PlayerSlot) {
+    root.spawn(NodeBundle {
+        style: Style {
+            display: Display::None,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(10.0),
+            ..default()
+        },
+        ..default()
+    })
+    .insert(HeatGauge(slot))
+    .with_children(|gauge| {
+        gauge.spawn(hud_text(HEAT_GAUGE_TEXT));
+    })
+    .with_children(|gauge| heat_gauge_bar(gauge, slot));
+}
+
+fn heat_gauge_bar(root: &mut ChildBuilder, slot: PlayerSlot) {
+    root.spawn(NodeBundle {
+        style: Style {
+            width: Val::Px(GAUGE_WIDTH_PX),
+            height: Val::Px(GAUGE_HEIGHT_PX),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        border_color: GAUGE_BORDER_COLOR.into(),
+        ..default()
+    })
+    .with_children(|bar| {
+        bar.spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: HEAT_GAUGE_FILL_COLOR.into(),
+            ..default()
+        })
+        .insert(HeatGaugeFill(slot));
+    });
+}
+
 // const CONTROLS_TEXT_SIZE: f32 = 20.;
 // const CONTROLS_TEXT_COLOR: Color = Color::WHITE;
 // fn ui_zone_1(root: &mut ChildBuilder) {
@@ -32,6 +32,11 @@ impl From<Speed> for f32 {
         val.0
     }
 }
+impl From<&Speed> for f32 {
+    fn from(val: &Speed) -> Self {
+        val.0
+    }
+}
 
 #[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
@@ -50,11 +55,11 @@ pub struct MovableX {
 
 impl MovableX {
     pub fn can_move_left(&self, transform: &mut Transform) -> bool {
-        &transform.translation.x > &self.bound.min
+        transform.translation.x > self.bound.min
     }
 
     pub fn can_move_right(&self, transform: &mut Transform) -> bool {
-        &transform.translation.x < &self.bound.max
+        transform.translation.x < self.bound.max
     }
 
     pub fn move_left(&self, transform: &mut Transform, timer: &Res<Time>) {
@@ -66,6 +71,14 @@ impl MovableX {
         let new_x: f32 = transform.translation.x + self.speed.0 * timer.delta_seconds();
         transform.translation.x = new_x.min(self.bound.max);
     }
+
+    /// Moves by `distance` within the bound. Returns `false` if the bound
+    /// stopped the movement.
+    pub fn shift(&self, transform: &mut Transform, distance: f32) -> bool {
+        let new_x: f32 = transform.translation.x + distance;
+        transform.translation.x = new_x.clamp(self.bound.min, self.bound.max);
+        transform.translation.x == new_x
+    }
 }
 
 #[derive(Component, Debug, Default, Reflect)]
@@ -77,11 +90,11 @@ pub struct MovableY {
 
 impl MovableY {
     pub fn can_move_down(&self, transform: &mut Transform) -> bool {
-        &transform.translation.y > &self.bound.min
+        transform.translation.y > self.bound.min
     }
 
     pub fn can_move_up(&self, transform: &mut Transform) -> bool {
-        &transform.translation.y < &self.bound.max
+        transform.translation.y < self.bound.max
     }
 
     pub fn move_down(&self, transform: &mut Transform, timer: &Res<Time>) {
@@ -93,6 +106,14 @@ impl MovableY {
         let new_y: f32 = transform.translation.y + self.speed.0 * timer.delta_seconds();
         transform.translation.y = new_y.min(self.bound.max);
     }
+
+    /// Moves by `distance` within the bound. Returns `false` if the bound
+    /// stopped the movement.
+    pub fn shift(&self, transform: &mut Transform, distance: f32) -> bool {
+        let new_y: f32 = transform.translation.y + distance;
+        transform.translation.y = new_y.clamp(self.bound.min, self.bound.max);
+        transform.translation.y == new_y
+    }
 }
 
 #[derive(Component, Debug, Default, Reflect)]
@@ -105,9 +126,9 @@ impl From<Vec2> for EntitySize {
         Self { vec: value }
     }
 }
-impl Into<Vec2> for EntitySize {
-    fn into(self) -> Vec2 {
-        self.vec
+impl From<EntitySize> for Vec2 {
+    fn from(value: EntitySize) -> Self {
+        value.vec
     }
 }
 impl From<(f32, f32)> for EntitySize {
@@ -139,21 +160,136 @@ impl Health {
     pub fn dead(&self) -> bool {
         self.actual <= 0.
     }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.actual = (self.actual + amount).min(self.max);
+    }
 }
 
 #[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
 pub struct Velocity {
-    pub speed: f32,
+    pub linear: Vec2,
 }
 
 impl Velocity {
-    pub fn new(speed: f32) -> Self {
-        Self { speed }
+    pub fn new(linear: Vec2) -> Self {
+        Self { linear }
+    }
+
+    pub fn from_transform(transform: &Transform, speed: f32) -> Self {
+        Self::new(transform.up().truncate() * speed)
     }
 
     pub fn advance(&self, timer: &Res<Time>, transform: &mut Transform) {
-        transform.translation += transform.up() * self.speed * timer.delta_seconds();
+        transform.translation += self.linear.extend(0.) * timer.delta_seconds();
+        // keep the visual rotation along the movement direction
+        if self.linear != Vec2::ZERO {
+            transform.rotation = Quat::from_rotation_z(Vec2::Y.angle_between(self.linear));
+        }
+    }
+}
+
+/// Forces applied to the velocity over time. Default is no gravity and no
+/// drag, so the projectile flies straight.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Ballistics {
+    pub gravity: f32,
+    pub drag: f32,
+}
+
+impl Ballistics {
+    pub fn apply(&self, timer: &Res<Time>, velocity: &mut Velocity) {
+        let delta = timer.delta_seconds();
+        velocity.linear.y -= self.gravity * delta;
+        velocity.linear *= (1. - self.drag * delta).max(0.);
+    }
+}
+
+/// Conditions that detonate the projectile on its own, without hitting
+/// anything.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Detonation {
+    pub fuse: Option<Timer>,
+    /// Detonate when descending below this height.
+    pub height: Option<f32>,
+}
+
+impl Detonation {
+    pub fn tick(&mut self, timer: &Res<Time>) {
+        if let Some(fuse) = self.fuse.as_mut() {
+            fuse.tick(timer.delta());
+        }
+    }
+
+    pub fn triggered(&self, transform: &Transform, velocity: &Velocity) -> bool {
+        let fuse_burnt = self.fuse.as_ref().is_some_and(|fuse| fuse.finished());
+        let height_reached = self
+            .height
+            .is_some_and(|height| velocity.linear.y < 0. && transform.translation.y <= height);
+        fuse_burnt || height_reached
+    }
+}
+
+/// Bounces left before the projectile stops reflecting off the arena walls,
+/// and the fraction of speed and damage kept after each bounce.
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Ricochet {
+    pub bounces_left: usize,
+    pub speed_retention: f32,
+    pub damage_retention: f32,
+}
+
+impl Ricochet {
+    /// Reflects the projectile off the wall it crossed, if any bounces left.
+    pub fn try_bounce(
+        &mut self,
+        bound: &MovementBound,
+        transform: &mut Transform,
+        velocity: &mut Velocity,
+        damage_multiplier: &mut DamageMultiplier,
+    ) {
+        if self.bounces_left == 0 {
+            return;
+        }
+        let x = transform.translation.x;
+        let hit_wall =
+            (x < bound.min && velocity.linear.x < 0.) || (x > bound.max && velocity.linear.x > 0.);
+        if !hit_wall {
+            return;
+        }
+        self.bounces_left -= 1;
+        transform.translation.x = x.clamp(bound.min, bound.max);
+        velocity.linear.x = -velocity.linear.x;
+        velocity.linear *= self.speed_retention;
+        damage_multiplier.scale(self.damage_retention);
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct DamageMultiplier(f32);
+impl DamageMultiplier {
+    pub fn scale(&mut self, factor: f32) {
+        self.0 *= factor;
+    }
+}
+impl Default for DamageMultiplier {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+impl From<f32> for DamageMultiplier {
+    fn from(value: f32) -> Self {
+        Self(value)
+    }
+}
+impl From<&DamageMultiplier> for f32 {
+    fn from(val: &DamageMultiplier) -> Self {
+        val.0
     }
 }
 
@@ -1,27 +1,269 @@
+pub mod attachment;
+pub mod beam;
+pub mod heat;
 pub mod parameters;
+pub mod secondary;
+pub mod spread;
 
+use attachment::WeaponAttachment;
+use attachment::WeaponStatModifier;
+use bevy::input::ButtonInput;
 use bevy::prelude::*;
+use bevy::time::Stopwatch;
 use bevy_prng::WyRand;
 use bevy_rand::resource::GlobalEntropy;
+use heat::WeaponHeat;
 use parameters::*;
 use rand::Rng;
+use spread::WeaponSpread;
 
 use super::*;
 
-#[derive(Component, Debug, Default, Reflect)]
+#[derive(Component, Debug, Clone, Default, Reflect)]
 #[reflect(Component)]
 pub struct WeaponShotParameters {
     fire_rate: f32,
-    reload_rate: f32,
-    mag_capacity: usize,
-    spread_deg: f32,
+    fire_mode: WeaponFireMode,
+    resource: WeaponShotResource,
+    spread: WeaponSpread,
     projectiles: usize,
     damage: WeaponShotDamage,
     shot_speed: f32,
     shot_size: Vec2,
+    ballistics: WeaponShotBallistics,
+    ricochet: WeaponShotRicochet,
+    crit_chance: f32,
+    crit_multiplier: f32,
+    /// Damage is randomly scaled by up to this fraction in both directions.
+    damage_variance: f32,
 }
 
-#[derive(Component, Debug, Reflect)]
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub enum WeaponFireMode {
+    /// Weapon fires as long as the trigger is held.
+    #[default]
+    Automatic,
+    /// One shot per trigger pull.
+    SemiAutomatic,
+    /// A number of shots per trigger pull, `delay` seconds apart. Fire rate
+    /// limits the bursts themselves.
+    Burst { shots: usize, delay: f32 },
+    /// Weapon is charged while the trigger is held and fires on release.
+    /// Damage scales linearly up to `max_damage_multiplier` within
+    /// `max_charge_time` seconds.
+    Charge {
+        max_charge_time: f32,
+        max_damage_multiplier: f32,
+    },
+}
+
+impl WeaponFireMode {
+    pub const fn burst(shots: usize, delay: f32) -> Self {
+        Self::Burst { shots, delay }
+    }
+
+    pub const fn charge(max_charge_time: f32, max_damage_multiplier: f32) -> Self {
+        Self::Charge {
+            max_charge_time,
+            max_damage_multiplier,
+        }
+    }
+}
+
+/// Trigger state for the current frame.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WeaponTrigger {
+    pub pressed: bool,
+    pub just_pressed: bool,
+    pub just_released: bool,
+}
+
+impl WeaponTrigger {
+    pub fn new<T>(input: &ButtonInput<T>, button: T) -> Self
+    where
+        T: Copy + Eq + std::hash::Hash + Send + Sync + 'static,
+    {
+        Self {
+            pressed: input.pressed(button),
+            just_pressed: input.just_pressed(button),
+            just_released: input.just_released(button),
+        }
+    }
+}
+
+/// Damage applied to a single target.
+#[derive(Event, Debug, Clone)]
+pub struct HitEvent {
+    pub target: Entity,
+    /// Player the damage came from.
+    pub shooter: Entity,
+    pub translation: Vec3,
+    pub damage: f32,
+    pub critical: bool,
+    pub explosive: bool,
+}
+
+/// Player a projectile or beam belongs to.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Shooter(pub Entity);
+
+/// Projectiles produced by a single weapon shot.
+pub struct WeaponShots {
+    pub shots: Vec<(Velocity, Transform)>,
+    pub sprite: SpriteBundle,
+    pub damage: ShotDamage,
+    pub damage_multiplier: f32,
+    pub ballistics: Ballistics,
+    pub detonation: Detonation,
+    pub ricochet: Ricochet,
+    /// Velocity change of the shooter.
+    pub recoil: Vec2,
+}
+
+/// Ballistic behaviour of the fired projectiles. Default is a straight shot
+/// without any fuse.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct WeaponShotBallistics {
+    gravity: f32,
+    drag: f32,
+    /// Seconds before the projectile detonates on its own, `0.` for no fuse.
+    fuse_time: f32,
+    /// Height above the launch point at which the descending projectile
+    /// detonates.
+    detonation_height: Option<f32>,
+}
+
+impl WeaponShotBallistics {
+    pub const fn straight() -> Self {
+        Self {
+            gravity: 0.,
+            drag: 0.,
+            fuse_time: 0.,
+            detonation_height: None,
+        }
+    }
+
+    pub const fn lobbed(gravity: f32, drag: f32) -> Self {
+        Self {
+            gravity,
+            drag,
+            ..Self::straight()
+        }
+    }
+
+    pub const fn with_fuse_time(mut self, fuse_time: f32) -> Self {
+        self.fuse_time = fuse_time;
+        self
+    }
+
+    pub const fn with_detonation_height(mut self, detonation_height: f32) -> Self {
+        self.detonation_height = Some(detonation_height);
+        self
+    }
+
+    fn ballistics(&self) -> Ballistics {
+        Ballistics {
+            gravity: self.gravity,
+            drag: self.drag,
+        }
+    }
+
+    fn detonation(&self, spawn_translation: Vec3) -> Detonation {
+        Detonation {
+            fuse: (self.fuse_time > 0.)
+                .then(|| Timer::from_seconds(self.fuse_time, TimerMode::Once)),
+            height: self
+                .detonation_height
+                .map(|height| spawn_translation.y + height),
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct WeaponShotRicochet {
+    bounces: usize,
+    speed_retention: f32,
+    damage_retention: f32,
+}
+
+impl WeaponShotRicochet {
+    pub const fn none() -> Self {
+        Self::new(0, 1., 1.)
+    }
+
+    pub const fn new(bounces: usize, speed_retention: f32, damage_retention: f32) -> Self {
+        Self {
+            bounces,
+            speed_retention,
+            damage_retention,
+        }
+    }
+
+    fn ricochet(&self) -> Ricochet {
+        Ricochet {
+            bounces_left: self.bounces,
+            speed_retention: self.speed_retention,
+            damage_retention: self.damage_retention,
+        }
+    }
+}
+
+/// What limits the sustained fire of the weapon.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub enum WeaponShotResource {
+    /// Weapon fires until the magazine is empty, then reloads.
+    Magazine { capacity: usize, reload_rate: f32 },
+    /// Each shot heats the weapon up, reaching the max heat locks the weapon
+    /// until it cools down completely.
+    Heat {
+        heat_per_shot: f32,
+        max_heat: f32,
+        dissipation_rate: f32,
+    },
+}
+
+impl Default for WeaponShotResource {
+    fn default() -> Self {
+        Self::magazine(0, 0.)
+    }
+}
+
+impl WeaponShotResource {
+    pub const fn magazine(capacity: usize, reload_rate: f32) -> Self {
+        Self::Magazine {
+            capacity,
+            reload_rate,
+        }
+    }
+
+    pub const fn heat(heat_per_shot: f32, max_heat: f32, dissipation_rate: f32) -> Self {
+        Self::Heat {
+            heat_per_shot,
+            max_heat,
+            dissipation_rate,
+        }
+    }
+}
+
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct WeaponBeamParameters {
+    damage_per_second: f32,
+    range: f32,
+    width: f32,
+    heat_per_second: f32,
+    max_heat: f32,
+    heat_dissipation_rate: f32,
+    crit_chance: f32,
+    crit_multiplier: f32,
+    damage_variance: f32,
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
 pub enum WeaponShotDamage {
     Direct(WeaponShotDamageDirect),
@@ -35,38 +277,127 @@ impl Default for WeaponShotDamage {
 }
 
 impl WeaponShotDamage {
+    fn modify_damage(&mut self, f: impl Fn(f32) -> f32) {
+        match self {
+            Self::Direct(direct) => direct.damage = f(direct.damage),
+            Self::Explosive(explosive) => {
+                explosive.damage = f(explosive.damage);
+                explosive.min_damage = f(explosive.min_damage).min(explosive.damage);
+            }
+        }
+    }
+
     pub const fn direct(damage: f32) -> Self {
         Self::Direct(WeaponShotDamageDirect { damage })
     }
 
     pub const fn explosive(damage: f32, damage_radius: f32) -> Self {
-        Self::Explosive(WeaponShotDamageExplosive {
-            damage,
-            damage_radius,
-            damage_degradation: WeaponShotDamageExplosiveDegradation::Linear,
-        })
+        Self::Explosive(WeaponShotDamageExplosive::new(damage, damage_radius))
     }
 }
 
-#[derive(Component, Debug, Default, Reflect)]
+#[derive(Component, Debug, Clone, Default, Reflect)]
 #[reflect(Component)]
 pub struct WeaponShotDamageDirect {
     damage: f32,
 }
 
-#[derive(Component, Debug, Default, Reflect)]
+#[derive(Component, Debug, Clone, Default, Reflect)]
 #[reflect(Component)]
 pub struct WeaponShotDamageExplosive {
     damage: f32,
     damage_radius: f32,
     damage_degradation: WeaponShotDamageExplosiveDegradation,
+    /// Enemies closer than this take the full damage; degradation starts
+    /// from this radius outwards.
+    full_damage_radius: f32,
+    /// Damage floor for any enemy caught within `damage_radius`.
+    min_damage: f32,
 }
 
-#[derive(Component, Debug, Default, Reflect)]
+impl WeaponShotDamageExplosive {
+    pub const fn new(damage: f32, damage_radius: f32) -> Self {
+        Self {
+            damage,
+            damage_radius,
+            damage_degradation: WeaponShotDamageExplosiveDegradation::Linear,
+            full_damage_radius: 0.,
+            min_damage: 0.,
+        }
+    }
+
+    pub const fn with_degradation(
+        mut self,
+        damage_degradation: WeaponShotDamageExplosiveDegradation,
+    ) -> Self {
+        self.damage_degradation = damage_degradation;
+        self
+    }
+
+    pub const fn with_full_damage_radius(mut self, full_damage_radius: f32) -> Self {
+        self.full_damage_radius = full_damage_radius;
+        self
+    }
+
+    pub const fn with_min_damage(mut self, min_damage: f32) -> Self {
+        self.min_damage = min_damage;
+        self
+    }
+
+    /// Damage dealt to an enemy at the given distance from the explosion
+    /// center, or `None` if the enemy is out of the damage radius.
+    pub fn damage_at(&self, distance: f32) -> Option<f32> {
+        if distance > self.damage_radius {
+            return None;
+        }
+        let falloff_width = self.damage_radius - self.full_damage_radius;
+        let t = if falloff_width > 0. {
+            ((distance - self.full_damage_radius) / falloff_width).clamp(0., 1.)
+        } else {
+            0.
+        };
+        let damage = self.damage_degradation.factor(t) * self.damage;
+        Some(damage.max(self.min_damage))
+    }
+}
+
+pub const WEAPON_EXPLOSIVE_CURVE_POINTS: usize = 8;
+
+#[derive(Component, Debug, Clone, Default, Reflect)]
 #[reflect(Component)]
 pub enum WeaponShotDamageExplosiveDegradation {
+    /// No degradation at all, full damage within the whole radius.
+    Constant,
     #[default]
     Linear,
+    Quadratic,
+    /// Never reaches zero within the radius: a quarter of the damage is still
+    /// dealt on the edge.
+    InverseSquare,
+    /// Damage multipliers sampled evenly from the full damage radius (first
+    /// point) to the damage radius (last point), linearly interpolated in
+    /// between.
+    Custom([f32; WEAPON_EXPLOSIVE_CURVE_POINTS]),
+}
+
+impl WeaponShotDamageExplosiveDegradation {
+    /// Damage multiplier for the normalized distance `t`, where `0.` is the
+    /// full damage radius and `1.` is the edge of the damage radius.
+    pub fn factor(&self, t: f32) -> f32 {
+        let factor = match self {
+            Self::Constant => 1.,
+            Self::Linear => 1. - t,
+            Self::Quadratic => (1. - t).powi(2),
+            Self::InverseSquare => 1. / (1. + t).powi(2),
+            Self::Custom(points) => {
+                let position = t * (WEAPON_EXPLOSIVE_CURVE_POINTS - 1) as f32;
+                let idx = (position.floor() as usize).min(WEAPON_EXPLOSIVE_CURVE_POINTS - 2);
+                let (from, to) = (points[idx], points[idx + 1]);
+                from + (to - from) * (position - idx as f32)
+            }
+        };
+        factor.max(0.)
+    }
 }
 
 #[derive(Component, Debug, Default, Reflect)]
@@ -83,21 +414,95 @@ pub struct Weapon {
     pub timer: Timer,
     timer_state: TimerState,
     shots_left: usize,
+    burst_left: usize,
+    charge: Option<Stopwatch>,
+    bloom_deg: f32,
+    trigger_held: bool,
+    pub heat: Option<WeaponHeat>,
+    /// Parameters of the weapon preset, without any attachments.
+    base_params: WeaponShotParameters,
+    attachments: Vec<WeaponAttachment>,
+    /// Temporary modifiers applied on top of the attachments, e.g. by
+    /// power-ups.
+    boosts: Vec<WeaponStatModifier>,
+    /// Final parameters computed from the preset, the attachments and the
+    /// boosts.
     pub shot_params: WeaponShotParameters,
 }
 
-// This is a WIP hack not to pass enemy collider into `try_apply_damage`
-use crate::encounter::arena::enemy::ENEMY_ENTITY_HEIGHT;
 impl Weapon {
     fn new(shot_params: WeaponShotParameters) -> Self {
+        let (shots_left, heat) = match shot_params.resource {
+            WeaponShotResource::Magazine { capacity, .. } => (capacity, None),
+            WeaponShotResource::Heat {
+                max_heat,
+                dissipation_rate,
+                ..
+            } => (0, Some(WeaponHeat::new(max_heat, dissipation_rate))),
+        };
         Self {
             timer: Timer::from_seconds(1. / shot_params.fire_rate, TimerMode::Once),
-            shots_left: shot_params.mag_capacity,
+            shots_left,
+            heat,
+            base_params: shot_params.clone(),
             shot_params,
             ..default()
         }
     }
 
+    pub fn attachments(&self) -> &[WeaponAttachment] {
+        &self.attachments
+    }
+
+    pub fn attach(&mut self, attachment: WeaponAttachment) -> &mut Self {
+        self.attachments.push(attachment);
+        self.apply_attachments();
+        self
+    }
+
+    pub fn detach(&mut self, attachment: WeaponAttachment) -> &mut Self {
+        if let Some(idx) = self.attachments.iter().position(|a| *a == attachment) {
+            self.attachments.remove(idx);
+            self.apply_attachments();
+        }
+        self
+    }
+
+    pub fn set_boosts(&mut self, boosts: Vec<WeaponStatModifier>) -> &mut Self {
+        self.boosts = boosts;
+        self.apply_attachments();
+        self
+    }
+
+    /// Refills the magazine or cools the weapon down completely.
+    pub fn refill(&mut self) -> &mut Self {
+        if let WeaponShotResource::Magazine { capacity, .. } = self.shot_params.resource {
+            self.shots_left = capacity;
+            self.timer_state = TimerState::InterShot;
+            self.timer
+                .set_duration(Duration::from_secs_f32(1. / self.shot_params.fire_rate));
+        }
+        if let Some(heat) = self.heat.as_mut() {
+            heat.cool_down();
+        }
+        self
+    }
+
+    fn apply_attachments(&mut self) {
+        let mut shot_params = self.base_params.with_attachments(&self.attachments);
+        if !self.boosts.is_empty() {
+            shot_params = shot_params.with_modifiers(&self.boosts);
+        }
+        self.shot_params = shot_params;
+        if let TimerState::InterShot = self.timer_state {
+            self.timer
+                .set_duration(Duration::from_secs_f32(1. / self.shot_params.fire_rate));
+        }
+        if let WeaponShotResource::Magazine { capacity, .. } = self.shot_params.resource {
+            self.shots_left = self.shots_left.min(capacity);
+        }
+    }
+
     fn sprite_bundle(&self) -> SpriteBundle {
         SpriteBundle {
             sprite: Sprite {
@@ -133,16 +538,152 @@ impl Weapon {
         Self::new(ROCKET_SHOT_PARAMS)
     }
 
+    pub fn mortar() -> Self {
+        Self::new(MORTAR_SHOT_PARAMS)
+    }
+
     pub fn tick(&mut self, timer: impl AsRef<Time>) -> &mut Self {
         self.timer.tick(timer.as_ref().delta());
+        // a charge kept from a release the weapon was not ready for waits
+        // for the trigger to be pressed again
+        if let Some(charge) = self.charge.as_mut().filter(|_| self.trigger_held) {
+            charge.tick(timer.as_ref().delta());
+        }
+        if let WeaponShotResource::Magazine {
+            capacity,
+            reload_rate,
+        } = self.shot_params.resource
+        {
+            self.reload(capacity, reload_rate);
+        }
+        if !self.trigger_held {
+            self.bloom_deg = self
+                .shot_params
+                .spread
+                .bloom_recover(self.bloom_deg, &timer);
+        }
+        if let Some(heat) = self.heat.as_mut() {
+            heat.dissipate(timer);
+        }
         self
     }
 
     pub fn shoot(
         &mut self,
-        spawn_translation: Vec3,
-        mut rng: ResMut<GlobalEntropy<WyRand>>,
-    ) -> Option<(Vec<(Velocity, Transform)>, SpriteBundle)> {
+        trigger: WeaponTrigger,
+        spawn_transform: Transform,
+        rng: &mut ResMut<GlobalEntropy<WyRand>>,
+    ) -> Option<WeaponShots> {
+        self.trigger_held = trigger.pressed;
+        match self.shot_params.fire_mode {
+            WeaponFireMode::Automatic if trigger.pressed => self.fire(spawn_transform, rng, 1.),
+            WeaponFireMode::SemiAutomatic if trigger.just_pressed => {
+                self.fire(spawn_transform, rng, 1.)
+            }
+            WeaponFireMode::Burst { shots, delay } => {
+                if trigger.just_pressed && self.burst_left == 0 {
+                    self.burst_left = shots;
+                }
+                if self.burst_left == 0 {
+                    return None;
+                }
+                let Some(weapon_shots) = self.fire(spawn_transform, rng, 1.) else {
+                    // burst is interrupted by the reload or the overheat
+                    let overheated = self.heat.as_ref().is_some_and(WeaponHeat::overheated);
+                    if matches!(self.timer_state, TimerState::Reload) || overheated {
+                        self.burst_left = 0;
+                    }
+                    return None;
+                };
+                self.burst_left -= 1;
+                let next_shot_delay = if self.burst_left > 0 {
+                    delay
+                } else {
+                    1. / self.shot_params.fire_rate
+                };
+                self.timer
+                    .set_duration(Duration::from_secs_f32(next_shot_delay));
+                self.timer.reset();
+                Some(weapon_shots)
+            }
+            WeaponFireMode::Charge {
+                max_charge_time,
+                max_damage_multiplier,
+            } => {
+                if trigger.just_pressed && self.charge.is_none() {
+                    self.charge = Some(Stopwatch::new());
+                }
+                if !trigger.just_released {
+                    return None;
+                }
+                let charge = self.charge.take()?;
+                let charge_ratio = (charge.elapsed_secs() / max_charge_time).min(1.);
+                let damage_multiplier = 1. + (max_damage_multiplier - 1.) * charge_ratio;
+                let weapon_shots = self.fire(spawn_transform, rng, damage_multiplier);
+                if weapon_shots.is_none() {
+                    // released while reloading or overheated, the charge is
+                    // kept for the next release
+                    self.charge = Some(charge);
+                }
+                weapon_shots
+            }
+            _ => None,
+        }
+    }
+
+    fn fire(
+        &mut self,
+        spawn_transform: Transform,
+        rng: &mut ResMut<GlobalEntropy<WyRand>>,
+        damage_multiplier: f32,
+    ) -> Option<WeaponShots> {
+        let ready = match self.shot_params.resource {
+            WeaponShotResource::Magazine { .. } => self.magazine_ready(),
+            WeaponShotResource::Heat { heat_per_shot, .. } => self.heat_ready(heat_per_shot),
+        };
+        if !ready {
+            return None;
+        }
+
+        let mut shots = vec![];
+        let offsets =
+            self.shot_params
+                .spread
+                .offsets(self.shot_params.projectiles, self.bloom_deg, rng);
+        for (angle_deg, lateral_offset) in offsets {
+            let mut transform = spawn_transform;
+            transform.translation += *transform.right() * lateral_offset;
+            transform.rotate_local_z(angle_deg.to_radians());
+            let velocity = Velocity::from_transform(&transform, self.shot_params.shot_speed);
+            shots.push((velocity, transform));
+        }
+        self.bloom_deg = self.shot_params.spread.bloom_up(self.bloom_deg);
+
+        Some(WeaponShots {
+            shots,
+            sprite: self.sprite_bundle(),
+            damage: self.shot_damage(),
+            damage_multiplier,
+            ballistics: self.shot_params.ballistics.ballistics(),
+            detonation: self
+                .shot_params
+                .ballistics
+                .detonation(spawn_transform.translation),
+            ricochet: self.shot_params.ricochet.ricochet(),
+            recoil: -spawn_transform.up().truncate() * self.shot_params.spread.recoil(),
+        })
+    }
+
+    fn shot_damage(&self) -> ShotDamage {
+        ShotDamage {
+            damage: self.shot_params.damage.clone(),
+            crit_chance: self.shot_params.crit_chance,
+            crit_multiplier: self.shot_params.crit_multiplier,
+            damage_variance: self.shot_params.damage_variance,
+        }
+    }
+
+    fn magazine_ready(&mut self) -> bool {
         match (
             self.shots_left > 0,
             self.timer.finished(),
@@ -152,25 +693,24 @@ impl Weapon {
             (true, true, TimerState::InterShot) => {
                 self.timer.reset();
                 self.shots_left -= 1;
-
-                let mut shots = vec![];
-                for _ in 0..self.shot_params.projectiles {
-                    let mut transform = Transform::from_translation(spawn_translation);
-                    let angle_range =
-                        (-self.shot_params.spread_deg / 2.0)..(self.shot_params.spread_deg / 2.0);
-                    transform.rotate_local_z(rng.gen_range(angle_range).to_radians());
-                    shots.push((Velocity::new(self.shot_params.shot_speed), transform));
-                }
-
-                Some((shots, self.sprite_bundle()))
+                true
             }
+            (_, _, _) => false,
+        }
+    }
+
+    fn reload(&mut self, capacity: usize, reload_rate: f32) {
+        match (
+            self.shots_left > 0,
+            self.timer.finished(),
+            &self.timer_state,
+        ) {
             // no shots left, time to reload
             (false, _, TimerState::InterShot) => {
                 self.timer_state = TimerState::Reload;
                 self.timer
-                    .set_duration(Duration::from_secs_f32(1. / self.shot_params.reload_rate));
+                    .set_duration(Duration::from_secs_f32(1. / reload_rate));
                 self.timer.reset();
-                None
             }
             // reload finished, get back firing
             (_, true, TimerState::Reload) => {
@@ -178,30 +718,80 @@ impl Weapon {
                 self.timer
                     .set_duration(Duration::from_secs_f32(1. / self.shot_params.fire_rate));
                 self.timer.reset();
-                self.shots_left = self.shot_params.mag_capacity;
-                None
+                self.shots_left = capacity;
             }
             // timer not finished yet
-            (_, _, _) => None,
+            (_, _, _) => (),
         }
     }
 
+    fn heat_ready(&mut self, heat_per_shot: f32) -> bool {
+        let Some(heat) = self.heat.as_mut() else {
+            return false;
+        };
+        // overheated weapon is locked until it cools down
+        if heat.overheated() || !self.timer.finished() {
+            return false;
+        }
+        self.timer.reset();
+        heat.heat_up(heat_per_shot);
+        true
+    }
+}
+
+/// Damage model carried by every fired projectile, so the hit does not depend
+/// on the weapon the player is holding at the moment.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct ShotDamage {
+    damage: WeaponShotDamage,
+    crit_chance: f32,
+    crit_multiplier: f32,
+    damage_variance: f32,
+}
+
+// This is a WIP hack not to pass enemy collider into `try_apply_damage`
+use crate::encounter::arena::enemy::ENEMY_ENTITY_HEIGHT;
+impl ShotDamage {
+    /// Rolls the damage variance and the critical hit for a single shot.
+    fn roll_damage(&self, rng: &mut ResMut<GlobalEntropy<WyRand>>) -> (f32, bool) {
+        roll_damage(
+            self.crit_chance,
+            self.crit_multiplier,
+            self.damage_variance,
+            rng,
+        )
+    }
+
     pub fn try_apply_damage(
         &self,
         self_transform: &Transform,
-        enemies: &mut Vec<(impl AsMut<Health>, &Transform)>,
-    ) -> Option<f32> {
-        match &self.shot_params.damage {
+        shooter: Entity,
+        damage_multiplier: f32,
+        detonated: bool,
+        enemies: &mut Vec<(Entity, impl AsMut<Health>, &Transform)>,
+        rng: &mut ResMut<GlobalEntropy<WyRand>>,
+    ) -> Option<Vec<HitEvent>> {
+        match &self.damage {
             // direct hit damage
             WeaponShotDamage::Direct(direct) => {
-                for (enemy_health, enemy_transform) in enemies.into_iter() {
+                for (enemy_entity, enemy_health, enemy_transform) in enemies.iter_mut() {
                     if self_transform
                         .translation
                         .distance(enemy_transform.translation)
                         <= ENEMY_ENTITY_HEIGHT / 2.
                     {
-                        enemy_health.as_mut().actual -= direct.damage;
-                        return Some(direct.damage);
+                        let (roll_multiplier, critical) = self.roll_damage(rng);
+                        let damage = direct.damage * damage_multiplier * roll_multiplier;
+                        enemy_health.as_mut().actual -= damage;
+                        return Some(vec![HitEvent {
+                            target: *enemy_entity,
+                            shooter,
+                            translation: enemy_transform.translation,
+                            damage,
+                            critical,
+                            explosive: false,
+                        }]);
                     }
                 }
                 None
@@ -210,32 +800,154 @@ impl Weapon {
             // explosive damage
             WeaponShotDamage::Explosive(explosive) => {
                 // detect if any enemy is triggering the shot explosive
-                if !enemies.iter_mut().any(|(_, enemy_transform)| -> bool {
-                    self_transform
-                        .translation
-                        .distance(enemy_transform.translation)
-                        <= ENEMY_ENTITY_HEIGHT / 2.
-                }) {
+                if !detonated
+                    && !enemies.iter_mut().any(|(_, _, enemy_transform)| -> bool {
+                        self_transform
+                            .translation
+                            .distance(enemy_transform.translation)
+                            <= ENEMY_ENTITY_HEIGHT / 2.
+                    })
+                {
                     return None;
                 }
-                // calculate and apply damage
-                let damage_done =
-                    enemies
-                        .iter_mut()
-                        .fold(0., |acc, (enemy_health, enemy_transform)| -> f32 {
-                            let enemy_distance = self_transform
-                                .translation
-                                .distance(enemy_transform.translation);
-                            if enemy_distance <= explosive.damage_radius {
-                                let damage = (1. - (enemy_distance / explosive.damage_radius))
-                                    * explosive.damage;
-                                enemy_health.as_mut().actual -= damage;
-                                return acc + damage;
-                            }
-                            acc
+                // calculate and apply damage, the whole explosion shares a
+                // single roll
+                let (roll_multiplier, critical) = self.roll_damage(rng);
+                let mut hits = vec![];
+                for (enemy_entity, enemy_health, enemy_transform) in enemies.iter_mut() {
+                    let enemy_distance = self_transform
+                        .translation
+                        .distance(enemy_transform.translation);
+                    if let Some(damage) = explosive.damage_at(enemy_distance) {
+                        let damage = damage * damage_multiplier * roll_multiplier;
+                        enemy_health.as_mut().actual -= damage;
+                        hits.push(HitEvent {
+                            target: *enemy_entity,
+                            shooter,
+                            translation: enemy_transform.translation,
+                            damage,
+                            critical,
+                            explosive: true,
                         });
-                Some(damage_done)
+                    }
+                }
+                Some(hits)
+            }
+        }
+    }
+}
+
+/// Rolls the damage variance and the critical hit for a single hit, returning
+/// the damage multiplier and whether the hit is critical.
+fn roll_damage(
+    crit_chance: f32,
+    crit_multiplier: f32,
+    damage_variance: f32,
+    rng: &mut ResMut<GlobalEntropy<WyRand>>,
+) -> (f32, bool) {
+    let variance_multiplier = if damage_variance > 0. {
+        1. + rng.gen_range(-damage_variance..damage_variance)
+    } else {
+        1.
+    };
+    let critical = rng.gen_bool(crit_chance.clamp(0., 1.) as f64);
+    let crit_multiplier = if critical { crit_multiplier } else { 1. };
+    (variance_multiplier * crit_multiplier, critical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn degradation_factors() {
+        use WeaponShotDamageExplosiveDegradation::*;
+        let custom = Custom([1., 1., 0.5, 0.5, 0.2, 0.2, 0., 0.]);
+        for (degradation, t, expected) in [
+            (Constant, 0., 1.),
+            (Constant, 0.5, 1.),
+            (Constant, 1., 1.),
+            (Linear, 0., 1.),
+            (Linear, 0.25, 0.75),
+            (Linear, 1., 0.),
+            (Quadratic, 0., 1.),
+            (Quadratic, 0.5, 0.25),
+            (Quadratic, 1., 0.),
+            (InverseSquare, 0., 1.),
+            (InverseSquare, 0.5, 1. / 2.25),
+            (InverseSquare, 1., 0.25),
+            // on the points
+            (custom.clone(), 0., 1.),
+            (custom.clone(), 2. / 7., 0.5),
+            (custom.clone(), 1., 0.),
+            // halfway between the points
+            (custom.clone(), 1.5 / 7., 0.75),
+            (custom.clone(), 5.5 / 7., 0.1),
+        ] {
+            assert_close(degradation.factor(t), expected);
+        }
+    }
+
+    #[test]
+    fn custom_degradation_does_not_go_negative() {
+        let degradation =
+            WeaponShotDamageExplosiveDegradation::Custom([1., 0., -1., -1., -1., -1., -1., -1.]);
+        assert_close(degradation.factor(0.5), 0.);
+    }
+
+    #[test]
+    fn damage_falls_off_past_the_full_damage_radius() {
+        let explosive = WeaponShotDamageExplosive::new(100., 50.).with_full_damage_radius(10.);
+        for (distance, expected) in [
+            (0., Some(100.)),
+            (10., Some(100.)),
+            (30., Some(50.)),
+            (50., Some(0.)),
+            (50.1, None),
+        ] {
+            let damage = explosive.damage_at(distance);
+            assert_eq!(damage.is_some(), expected.is_some(), "at {distance}");
+            if let (Some(damage), Some(expected)) = (damage, expected) {
+                assert_close(damage, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn full_damage_radius_covering_the_damage_radius_deals_full_damage() {
+        for full_damage_radius in [50., 80.] {
+            let explosive = WeaponShotDamageExplosive::new(100., 50.)
+                .with_full_damage_radius(full_damage_radius);
+            assert_eq!(explosive.damage_at(0.), Some(100.));
+            assert_eq!(explosive.damage_at(50.), Some(100.));
+            assert_eq!(explosive.damage_at(60.), None);
+        }
+    }
+
+    #[test]
+    fn min_damage_is_the_floor_within_the_damage_radius() {
+        let explosive = WeaponShotDamageExplosive::new(100., 50.)
+            .with_degradation(WeaponShotDamageExplosiveDegradation::Quadratic)
+            .with_min_damage(20.);
+        for (distance, expected) in [
+            (0., Some(100.)),
+            (25., Some(25.)),
+            (40., Some(20.)),
+            (50., Some(20.)),
+        ] {
+            let damage = explosive.damage_at(distance);
+            assert_eq!(damage.is_some(), expected.is_some(), "at {distance}");
+            if let (Some(damage), Some(expected)) = (damage, expected) {
+                assert_close(damage, expected);
             }
         }
+        assert_eq!(explosive.damage_at(51.), None);
     }
 }
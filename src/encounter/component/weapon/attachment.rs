@@ -0,0 +1,190 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum WeaponStat {
+    FireRate,
+    MagCapacity,
+    ReloadRate,
+    Spread,
+    Damage,
+    ShotSpeed,
+}
+
+/// Change of a single weapon stat. All the additive changes of a stat are
+/// summed up and applied first, then the result is multiplied by all the
+/// multipliers of the stat.
+#[derive(Debug, Clone, Copy, Reflect)]
+pub enum WeaponStatModifier {
+    Add(WeaponStat, f32),
+    Multiply(WeaponStat, f32),
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum WeaponAttachment {
+    ExtendedMagazine,
+    Choke,
+    HeavyRounds,
+    FastReload,
+}
+
+impl WeaponAttachment {
+    pub const ALL: [WeaponAttachment; 4] = [
+        Self::ExtendedMagazine,
+        Self::Choke,
+        Self::HeavyRounds,
+        Self::FastReload,
+    ];
+
+    pub fn modifiers(&self) -> &'static [WeaponStatModifier] {
+        use WeaponStat::*;
+        use WeaponStatModifier::*;
+        match self {
+            Self::ExtendedMagazine => &[Add(MagCapacity, 1.), Multiply(MagCapacity, 1.5)],
+            Self::Choke => &[Multiply(Spread, 0.6)],
+            Self::HeavyRounds => &[
+                Add(Damage, 5.),
+                Multiply(Damage, 1.2),
+                Multiply(ShotSpeed, 0.8),
+            ],
+            Self::FastReload => &[Multiply(ReloadRate, 1.5)],
+        }
+    }
+}
+
+impl WeaponShotParameters {
+    /// Final parameters of the weapon with the given attachments equipped.
+    pub fn with_attachments(&self, attachments: &[WeaponAttachment]) -> Self {
+        let modifiers: Vec<WeaponStatModifier> = attachments
+            .iter()
+            .flat_map(|attachment| attachment.modifiers().iter().copied())
+            .collect();
+        self.with_modifiers(&modifiers)
+    }
+
+    pub fn with_modifiers(&self, modifiers: &[WeaponStatModifier]) -> Self {
+        let mut shot_params = self.clone();
+        for stat in [
+            WeaponStat::FireRate,
+            WeaponStat::MagCapacity,
+            WeaponStat::ReloadRate,
+            WeaponStat::Spread,
+            WeaponStat::Damage,
+            WeaponStat::ShotSpeed,
+        ] {
+            let (add, multiply) = modifiers
+                .iter()
+                .fold((0., 1.), |(add, multiply), modifier| match *modifier {
+                    WeaponStatModifier::Add(s, value) if s == stat => (add + value, multiply),
+                    WeaponStatModifier::Multiply(s, value) if s == stat => (add, multiply * value),
+                    _ => (add, multiply),
+                });
+            if add != 0. || multiply != 1. {
+                shot_params.modify(stat, |value| ((value + add) * multiply).max(0.));
+            }
+        }
+        shot_params
+    }
+
+    fn modify(&mut self, stat: WeaponStat, f: impl Fn(f32) -> f32) {
+        match stat {
+            WeaponStat::FireRate => self.fire_rate = f(self.fire_rate),
+            WeaponStat::MagCapacity => {
+                if let WeaponShotResource::Magazine { capacity, .. } = &mut self.resource {
+                    *capacity = (f(*capacity as f32).round() as usize).max(1);
+                }
+            }
+            WeaponStat::ReloadRate => {
+                if let WeaponShotResource::Magazine { reload_rate, .. } = &mut self.resource {
+                    *reload_rate = f(*reload_rate);
+                }
+            }
+            WeaponStat::Spread => self.spread.modify_spread(f),
+            WeaponStat::Damage => self.damage.modify_damage(f),
+            WeaponStat::ShotSpeed => self.shot_speed = f(self.shot_speed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magazine(shot_params: &WeaponShotParameters) -> (usize, f32) {
+        match shot_params.resource {
+            WeaponShotResource::Magazine {
+                capacity,
+                reload_rate,
+            } => (capacity, reload_rate),
+            WeaponShotResource::Heat { .. } => panic!("Expected magazine weapon"),
+        }
+    }
+
+    fn direct_damage(shot_params: &WeaponShotParameters) -> f32 {
+        match &shot_params.damage {
+            WeaponShotDamage::Direct(direct) => direct.damage,
+            WeaponShotDamage::Explosive(_) => panic!("Expected direct damage"),
+        }
+    }
+
+    #[test]
+    fn no_attachments_keep_base_stats() {
+        let shot_params = RIFLE_SHOT_PARAMS.with_attachments(&[]);
+        assert_eq!(shot_params.fire_rate, RIFLE_SHOT_PARAMS.fire_rate);
+        assert_eq!(shot_params.shot_speed, RIFLE_SHOT_PARAMS.shot_speed);
+        assert_eq!(magazine(&shot_params), magazine(&RIFLE_SHOT_PARAMS));
+        assert_eq!(
+            direct_damage(&shot_params),
+            direct_damage(&RIFLE_SHOT_PARAMS)
+        );
+    }
+
+    #[test]
+    fn extended_magazine_adds_then_multiplies_capacity() {
+        let shot_params = RIFLE_SHOT_PARAMS.with_attachments(&[WeaponAttachment::ExtendedMagazine]);
+        // (30 + 1) * 1.5 = 46.5, rounded
+        assert_eq!(magazine(&shot_params).0, 47);
+    }
+
+    #[test]
+    fn heavy_rounds_trade_shot_speed_for_damage() {
+        let shot_params = RIFLE_SHOT_PARAMS.with_attachments(&[WeaponAttachment::HeavyRounds]);
+        assert_eq!(direct_damage(&shot_params), (15. + 5.) * 1.2);
+        assert_eq!(shot_params.shot_speed, 500. * 0.8);
+    }
+
+    #[test]
+    fn stacked_attachments_multiply() {
+        let shot_params = RIFLE_SHOT_PARAMS
+            .with_attachments(&[WeaponAttachment::FastReload, WeaponAttachment::FastReload]);
+        assert_eq!(magazine(&shot_params).1, 0.6 * (1.5 * 1.5));
+    }
+
+    #[test]
+    fn additions_apply_before_multipliers_regardless_of_order() {
+        let shot_params = RIFLE_SHOT_PARAMS.with_modifiers(&[
+            WeaponStatModifier::Multiply(WeaponStat::FireRate, 2.),
+            WeaponStatModifier::Add(WeaponStat::FireRate, 1.),
+        ]);
+        assert_eq!(shot_params.fire_rate, (10. + 1.) * 2.);
+    }
+
+    #[test]
+    fn stats_do_not_go_negative() {
+        let shot_params = RIFLE_SHOT_PARAMS
+            .with_modifiers(&[WeaponStatModifier::Add(WeaponStat::ShotSpeed, -1000.)]);
+        assert_eq!(shot_params.shot_speed, 0.);
+    }
+
+    #[test]
+    fn attachments_do_not_change_heat_weapons_magazine() {
+        let shot_params = MACHINE_GUN_SHOT_PARAMS.with_attachments(&[
+            WeaponAttachment::ExtendedMagazine,
+            WeaponAttachment::FastReload,
+        ]);
+        assert!(matches!(
+            shot_params.resource,
+            WeaponShotResource::Heat { .. }
+        ));
+    }
+}
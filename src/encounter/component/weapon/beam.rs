@@ -0,0 +1,129 @@
+use super::heat::WeaponHeat;
+use super::*;
+
+/// Hitscan weapon: a continuous beam damaging whatever it hits first while
+/// the fire is held. Limited by heat instead of a magazine.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct BeamWeapon {
+    pub heat: WeaponHeat,
+    pub beam_params: WeaponBeamParameters,
+}
+
+impl BeamWeapon {
+    fn new(beam_params: WeaponBeamParameters) -> Self {
+        Self {
+            heat: WeaponHeat::new(beam_params.max_heat, beam_params.heat_dissipation_rate),
+            beam_params,
+        }
+    }
+
+    pub fn sprite_bundle(&self) -> SpriteBundle {
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::CYAN,
+                custom_size: Some(Vec2::new(self.beam_params.width, 0.)),
+                anchor: bevy::sprite::Anchor::BottomCenter,
+                ..Sprite::default()
+            },
+            ..SpriteBundle::default()
+        }
+    }
+
+    pub fn laser() -> Self {
+        Self::new(LASER_BEAM_PARAMS)
+    }
+
+    pub fn width(&self) -> f32 {
+        self.beam_params.width
+    }
+
+    pub fn tick(&mut self, timer: impl AsRef<Time>) -> &mut Self {
+        self.heat.dissipate(timer);
+        self
+    }
+
+    /// Heats the weapon up for the current frame. Returns `false` if the
+    /// weapon is overheated and the beam should not be drawn.
+    pub fn fire(&mut self, timer: impl AsRef<Time>) -> bool {
+        if self.heat.overheated() {
+            return false;
+        }
+        self.heat
+            .heat_up(self.beam_params.heat_per_second * timer.as_ref().delta_seconds());
+        true
+    }
+
+    /// Casts the beam from `origin` along `direction` against the targets
+    /// given by their translation and size, returning the first target hit
+    /// (if any) with its translation, and the resulting beam length.
+    pub fn raycast(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        targets: impl IntoIterator<Item = (Entity, Vec3, Vec2)>,
+    ) -> (Option<(Entity, Vec3)>, f32) {
+        let origin = origin.truncate();
+        let direction = direction.truncate().normalize_or_zero();
+        let mut hit = None;
+        let mut length = self.beam_params.range;
+        for (target, target_translation, target_size) in targets {
+            let Some(distance) = ray_box_distance(
+                origin,
+                direction,
+                target_translation.truncate(),
+                target_size,
+            ) else {
+                continue;
+            };
+            if distance < length {
+                length = distance;
+                hit = Some((target, target_translation));
+            }
+        }
+        (hit, length)
+    }
+
+    /// Damage dealt to the hit target in the current frame, with the variance
+    /// and the critical hit rolled like for the projectiles.
+    pub fn damage(
+        &self,
+        timer: impl AsRef<Time>,
+        rng: &mut ResMut<GlobalEntropy<WyRand>>,
+    ) -> (f32, bool) {
+        let (roll_multiplier, critical) = roll_damage(
+            self.beam_params.crit_chance,
+            self.beam_params.crit_multiplier,
+            self.beam_params.damage_variance,
+            rng,
+        );
+        let damage = self.beam_params.damage_per_second * timer.as_ref().delta_seconds();
+        (damage * roll_multiplier, critical)
+    }
+}
+
+/// Distance along the ray to the axis aligned box of the given `size`
+/// centered at `center`, `None` if the ray misses it. Zero if the ray starts
+/// inside the box.
+fn ray_box_distance(origin: Vec2, direction: Vec2, center: Vec2, size: Vec2) -> Option<f32> {
+    let min = center - size / 2.;
+    let max = center + size / 2.;
+    let mut near = 0_f32;
+    let mut far = f32::INFINITY;
+    for axis in 0..2 {
+        if direction[axis] == 0. {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let to_min = (min[axis] - origin[axis]) / direction[axis];
+        let to_max = (max[axis] - origin[axis]) / direction[axis];
+        near = near.max(to_min.min(to_max));
+        far = far.min(to_min.max(to_max));
+        if near > far {
+            return None;
+        }
+    }
+    Some(near)
+}
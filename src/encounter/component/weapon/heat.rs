@@ -0,0 +1,54 @@
+use super::*;
+
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct WeaponHeat {
+    pub max: f32,
+    pub actual: f32,
+    dissipation_rate: f32,
+    overheated: bool,
+}
+
+impl WeaponHeat {
+    pub const fn new(max: f32, dissipation_rate: f32) -> Self {
+        Self {
+            max,
+            actual: 0.,
+            dissipation_rate,
+            overheated: false,
+        }
+    }
+
+    pub fn ratio(&self) -> f32 {
+        if self.max > 0. {
+            self.actual / self.max
+        } else {
+            0.
+        }
+    }
+
+    /// Weapon reached max heat and is locked until it fully cools down.
+    pub fn overheated(&self) -> bool {
+        self.overheated
+    }
+
+    pub fn heat_up(&mut self, amount: f32) {
+        self.actual = (self.actual + amount).min(self.max);
+        if self.actual >= self.max {
+            self.overheated = true;
+        }
+    }
+
+    pub fn cool_down(&mut self) {
+        self.actual = 0.;
+        self.overheated = false;
+    }
+
+    pub fn dissipate(&mut self, timer: impl AsRef<Time>) {
+        self.actual =
+            (self.actual - self.dissipation_rate * timer.as_ref().delta_seconds()).max(0.);
+        if self.overheated && self.actual <= 0. {
+            self.overheated = false;
+        }
+    }
+}
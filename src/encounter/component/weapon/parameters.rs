@@ -1,62 +1,157 @@
+use super::spread::WeaponSpread;
 use super::*;
 
 pub const SHOTGUN_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
     fire_rate: 1.,
-    reload_rate: 0.6,
-    mag_capacity: 5,
-    spread_deg: 30.,
+    fire_mode: WeaponFireMode::SemiAutomatic,
+    resource: WeaponShotResource::magazine(5, 0.6),
+    spread: WeaponSpread::fan(30.).with_recoil(300.),
     projectiles: 10,
     damage: WeaponShotDamage::direct(10.),
     shot_speed: 500.,
     shot_size: Vec2::new(2., 2.),
+    ballistics: WeaponShotBallistics::straight(),
+    ricochet: WeaponShotRicochet::new(1, 0.7, 0.5),
+    crit_chance: 0.05,
+    crit_multiplier: 2.,
+    damage_variance: 0.2,
 };
 pub const RIFLE_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
     fire_rate: 10.,
-    reload_rate: 0.6,
-    mag_capacity: 30,
-    spread_deg: 5.,
+    fire_mode: WeaponFireMode::burst(3, 0.07),
+    resource: WeaponShotResource::magazine(30, 0.6),
+    spread: WeaponSpread::random(2.)
+        .with_bloom(1., 6., 15.)
+        .with_recoil(40.),
     projectiles: 1,
     damage: WeaponShotDamage::direct(15.),
     shot_speed: 500.,
     shot_size: Vec2::new(2., 6.),
+    ballistics: WeaponShotBallistics::straight(),
+    ricochet: WeaponShotRicochet::new(2, 0.9, 0.8),
+    crit_chance: 0.15,
+    crit_multiplier: 2.,
+    damage_variance: 0.1,
 };
 pub const MACHINE_GUN_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
     fire_rate: 15.,
-    reload_rate: 0.2,
-    mag_capacity: 100,
-    spread_deg: 7.,
+    fire_mode: WeaponFireMode::Automatic,
+    resource: WeaponShotResource::heat(5., 100., 25.),
+    spread: WeaponSpread::random(3.)
+        .with_bloom(0.5, 12., 20.)
+        .with_recoil(30.),
     projectiles: 1,
     damage: WeaponShotDamage::direct(15.),
     shot_speed: 500.,
     shot_size: Vec2::new(2., 6.),
+    ballistics: WeaponShotBallistics::straight(),
+    ricochet: WeaponShotRicochet::none(),
+    crit_chance: 0.05,
+    crit_multiplier: 1.5,
+    damage_variance: 0.2,
 };
 pub const AUTOCANNON_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
     fire_rate: 3.,
-    reload_rate: 0.2,
-    mag_capacity: 10,
-    spread_deg: 5.,
+    fire_mode: WeaponFireMode::Automatic,
+    resource: WeaponShotResource::magazine(10, 0.2),
+    spread: WeaponSpread::random(5.),
     projectiles: 1,
     damage: WeaponShotDamage::explosive(50., 100.),
     shot_speed: 400.,
     shot_size: Vec2::new(3., 9.),
+    ballistics: WeaponShotBallistics::straight(),
+    ricochet: WeaponShotRicochet::none(),
+    crit_chance: 0.1,
+    crit_multiplier: 1.5,
+    damage_variance: 0.1,
 };
 pub const CANNON_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
     fire_rate: 1.,
-    reload_rate: 0.5,
-    mag_capacity: 1,
-    spread_deg: 5.,
+    fire_mode: WeaponFireMode::charge(1.5, 2.5),
+    resource: WeaponShotResource::magazine(1, 0.5),
+    spread: WeaponSpread::random(5.).with_recoil(250.),
     projectiles: 1,
-    damage: WeaponShotDamage::explosive(150., 150.),
+    damage: WeaponShotDamage::Explosive(
+        WeaponShotDamageExplosive::new(150., 150.)
+            .with_degradation(WeaponShotDamageExplosiveDegradation::Quadratic)
+            .with_full_damage_radius(30.),
+    ),
     shot_speed: 400.,
     shot_size: Vec2::new(5., 11.),
+    ballistics: WeaponShotBallistics::straight(),
+    ricochet: WeaponShotRicochet::none(),
+    crit_chance: 0.1,
+    crit_multiplier: 2.,
+    damage_variance: 0.1,
 };
 pub const ROCKET_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
     fire_rate: 1.,
-    reload_rate: 0.3,
-    mag_capacity: 1,
-    spread_deg: 5.,
+    fire_mode: WeaponFireMode::SemiAutomatic,
+    resource: WeaponShotResource::magazine(1, 0.3),
+    spread: WeaponSpread::random(5.),
     projectiles: 1,
-    damage: WeaponShotDamage::explosive(200., 200.),
+    damage: WeaponShotDamage::Explosive(
+        WeaponShotDamageExplosive::new(200., 200.)
+            .with_degradation(WeaponShotDamageExplosiveDegradation::InverseSquare)
+            .with_full_damage_radius(20.)
+            .with_min_damage(20.),
+    ),
     shot_speed: 200.,
     shot_size: Vec2::new(7., 13.),
+    ballistics: WeaponShotBallistics::straight(),
+    ricochet: WeaponShotRicochet::none(),
+    crit_chance: 0.05,
+    crit_multiplier: 1.5,
+    damage_variance: 0.1,
+};
+pub const LASER_BEAM_PARAMS: WeaponBeamParameters = WeaponBeamParameters {
+    damage_per_second: 60.,
+    range: 1000.,
+    width: 4.,
+    heat_per_second: 30.,
+    max_heat: 100.,
+    heat_dissipation_rate: 20.,
+    crit_chance: 0.05,
+    crit_multiplier: 2.,
+    damage_variance: 0.1,
+};
+pub const MORTAR_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
+    fire_rate: 0.8,
+    fire_mode: WeaponFireMode::SemiAutomatic,
+    resource: WeaponShotResource::magazine(3, 0.4),
+    spread: WeaponSpread::random(20.),
+    projectiles: 1,
+    damage: WeaponShotDamage::explosive(120., 120.),
+    shot_speed: 650.,
+    shot_size: Vec2::new(8., 8.),
+    ballistics: WeaponShotBallistics::lobbed(400., 0.1)
+        .with_fuse_time(4.)
+        .with_detonation_height(300.),
+    ricochet: WeaponShotRicochet::none(),
+    crit_chance: 0.1,
+    crit_multiplier: 1.5,
+    damage_variance: 0.15,
+};
+pub const GRENADE_MAX_CHARGES: usize = 3;
+pub const GRENADE_SHOT_PARAMS: WeaponShotParameters = WeaponShotParameters {
+    fire_rate: 1.,
+    fire_mode: WeaponFireMode::SemiAutomatic,
+    resource: WeaponShotResource::magazine(1, 1.),
+    spread: WeaponSpread::random(0.),
+    projectiles: 1,
+    damage: WeaponShotDamage::Explosive(
+        WeaponShotDamageExplosive::new(250., 200.)
+            .with_degradation(WeaponShotDamageExplosiveDegradation::Quadratic)
+            .with_full_damage_radius(50.)
+            .with_min_damage(25.),
+    ),
+    shot_speed: 700.,
+    shot_size: Vec2::new(10., 10.),
+    ballistics: WeaponShotBallistics::lobbed(500., 0.)
+        .with_fuse_time(3.)
+        .with_detonation_height(350.),
+    ricochet: WeaponShotRicochet::none(),
+    crit_chance: 0.,
+    crit_multiplier: 1.,
+    damage_variance: 0.1,
 };
@@ -0,0 +1,57 @@
+use super::*;
+
+/// Weapon in the secondary slot, limited by the number of charges in
+/// addition to its own fire rate.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct SecondaryWeapon {
+    pub weapon: Weapon,
+    charges: usize,
+    max_charges: usize,
+}
+
+impl SecondaryWeapon {
+    fn new(weapon: Weapon, max_charges: usize) -> Self {
+        Self {
+            weapon,
+            charges: max_charges,
+            max_charges,
+        }
+    }
+
+    pub fn grenade() -> Self {
+        Self::new(Weapon::new(GRENADE_SHOT_PARAMS), GRENADE_MAX_CHARGES)
+    }
+
+    pub fn charges(&self) -> usize {
+        self.charges
+    }
+
+    pub fn max_charges(&self) -> usize {
+        self.max_charges
+    }
+
+    pub fn refill(&mut self, charges: usize) -> &mut Self {
+        self.charges = (self.charges + charges).min(self.max_charges);
+        self
+    }
+
+    pub fn tick(&mut self, timer: impl AsRef<Time>) -> &mut Self {
+        self.weapon.tick(timer);
+        self
+    }
+
+    pub fn shoot(
+        &mut self,
+        trigger: WeaponTrigger,
+        spawn_transform: Transform,
+        rng: &mut ResMut<GlobalEntropy<WyRand>>,
+    ) -> Option<WeaponShots> {
+        if self.charges == 0 {
+            return None;
+        }
+        let weapon_shots = self.weapon.shoot(trigger, spawn_transform, rng)?;
+        self.charges -= 1;
+        Some(weapon_shots)
+    }
+}
@@ -0,0 +1,127 @@
+use super::*;
+
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub enum WeaponSpreadPattern {
+    /// Each projectile gets a random angle within the spread.
+    #[default]
+    Random,
+    /// Projectiles are evenly distributed within the spread.
+    Fan,
+    /// Projectiles are evenly distributed around the full circle, spread is
+    /// ignored.
+    Ring,
+    /// Projectiles fly in parallel, evenly distributed along a line of the
+    /// given width across the shot direction. Spread is ignored.
+    Line { width: f32 },
+}
+
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct WeaponSpread {
+    pattern: WeaponSpreadPattern,
+    spread_deg: f32,
+    /// Extra spread added by each shot, up to `max_bloom_deg`. Applies to
+    /// `Random` and `Fan` patterns only.
+    bloom_per_shot_deg: f32,
+    max_bloom_deg: f32,
+    /// Bloom recovered per second while the trigger is released.
+    bloom_recovery_deg: f32,
+    /// Speed each shot pushes the shooter back with, against the shot
+    /// direction.
+    recoil: f32,
+}
+
+impl WeaponSpread {
+    const fn new(pattern: WeaponSpreadPattern, spread_deg: f32) -> Self {
+        Self {
+            pattern,
+            spread_deg,
+            bloom_per_shot_deg: 0.,
+            max_bloom_deg: 0.,
+            bloom_recovery_deg: 0.,
+            recoil: 0.,
+        }
+    }
+
+    pub const fn random(spread_deg: f32) -> Self {
+        Self::new(WeaponSpreadPattern::Random, spread_deg)
+    }
+
+    pub const fn fan(spread_deg: f32) -> Self {
+        Self::new(WeaponSpreadPattern::Fan, spread_deg)
+    }
+
+    pub const fn ring() -> Self {
+        Self::new(WeaponSpreadPattern::Ring, 0.)
+    }
+
+    pub const fn line(width: f32) -> Self {
+        Self::new(WeaponSpreadPattern::Line { width }, 0.)
+    }
+
+    pub const fn with_bloom(
+        mut self,
+        bloom_per_shot_deg: f32,
+        max_bloom_deg: f32,
+        bloom_recovery_deg: f32,
+    ) -> Self {
+        self.bloom_per_shot_deg = bloom_per_shot_deg;
+        self.max_bloom_deg = max_bloom_deg;
+        self.bloom_recovery_deg = bloom_recovery_deg;
+        self
+    }
+
+    pub const fn with_recoil(mut self, recoil: f32) -> Self {
+        self.recoil = recoil;
+        self
+    }
+
+    pub fn recoil(&self) -> f32 {
+        self.recoil
+    }
+
+    /// Applies `f` to both the base spread and the max bloom.
+    pub fn modify_spread(&mut self, f: impl Fn(f32) -> f32) {
+        self.spread_deg = f(self.spread_deg);
+        self.max_bloom_deg = f(self.max_bloom_deg);
+    }
+
+    pub fn bloom_up(&self, bloom_deg: f32) -> f32 {
+        (bloom_deg + self.bloom_per_shot_deg).min(self.max_bloom_deg)
+    }
+
+    pub fn bloom_recover(&self, bloom_deg: f32, timer: impl AsRef<Time>) -> f32 {
+        (bloom_deg - self.bloom_recovery_deg * timer.as_ref().delta_seconds()).max(0.)
+    }
+
+    /// Angle (in degrees) and lateral offset of each of the `projectiles`
+    /// relative to the shot direction.
+    pub fn offsets(
+        &self,
+        projectiles: usize,
+        bloom_deg: f32,
+        rng: &mut ResMut<GlobalEntropy<WyRand>>,
+    ) -> Vec<(f32, f32)> {
+        let spread_deg = self.spread_deg + bloom_deg;
+        // position of the projectile within the pattern, from 0. to 1.
+        let position = |n: usize| -> f32 {
+            if projectiles > 1 {
+                n as f32 / (projectiles - 1) as f32
+            } else {
+                0.5
+            }
+        };
+        (0..projectiles)
+            .map(|n| match self.pattern {
+                WeaponSpreadPattern::Random if spread_deg > 0. => {
+                    (rng.gen_range((-spread_deg / 2.)..(spread_deg / 2.)), 0.)
+                }
+                WeaponSpreadPattern::Random => (0., 0.),
+                WeaponSpreadPattern::Fan => ((position(n) - 0.5) * spread_deg, 0.),
+                WeaponSpreadPattern::Ring => (360. * n as f32 / projectiles as f32, 0.),
+                WeaponSpreadPattern::Line { width } => (0., (position(n) - 0.5) * width),
+            })
+            .collect()
+    }
+}
@@ -1 +1,20 @@
 pub mod ui;
+
+use bevy::prelude::*;
+
+/// Why the last encounter ended, shown on the game over screen.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameOverReason {
+    #[default]
+    OutOfLives,
+    BaseDestroyed,
+}
+
+impl GameOverReason {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::OutOfLives => "YOU RAN OUT OF LIVES",
+            Self::BaseDestroyed => "THE ENEMIES DESTROYED YOUR BASE",
+        }
+    }
+}
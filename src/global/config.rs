@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use super::input::InputMap;
+use super::settings::GameSettings;
+use crate::GameSystemSet;
+
+const CONFIG_FILE_PATH: &str = "pewpew.toml";
+
+#[derive(Error, Debug)]
+pub enum GameConfigError {
+    #[error("failed to read config: {0}")]
+    Read(#[from] config::ConfigError),
+    #[error("failed to serialize config: {0}")]
+    Serialize(#[from] toml::ser::Error),
+    #[error("failed to write config: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// Settings persisted between the game runs. Missing fields fall back to the
+/// defaults, so the file may contain only the changed settings.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub input: InputMap,
+    pub settings: GameSettings,
+}
+
+impl GameConfig {
+    pub fn load() -> Result<Self, GameConfigError> {
+        let config = config::Config::builder()
+            .add_source(
+                config::File::new(CONFIG_FILE_PATH, config::FileFormat::Toml).required(false),
+            )
+            .build()?
+            .try_deserialize()?;
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<(), GameConfigError> {
+        std::fs::write(CONFIG_FILE_PATH, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Loads the config file into [`GameConfig`] and the resources it is made of,
+/// and writes it back whenever any of them changes.
+pub struct GameConfigPlugin;
+
+impl Plugin for GameConfigPlugin {
+    fn build(&self, app: &mut App) {
+        let config = GameConfig::load().unwrap_or_else(|err| {
+            bevy::log::warn!("Using default config: {}", err);
+            GameConfig::default()
+        });
+        app.insert_resource(config.input.clone())
+            .insert_resource(config.settings.clone())
+            .insert_resource(config)
+            .add_systems(Update, save_config.in_set(GameSystemSet::Global));
+    }
+}
+
+fn save_config(
+    input_map: Res<InputMap>,
+    settings: Res<GameSettings>,
+    mut config: ResMut<GameConfig>,
+) {
+    let input_changed = input_map.is_changed() && !input_map.is_added();
+    let settings_changed = settings.is_changed() && !settings.is_added();
+    if !input_changed && !settings_changed {
+        return;
+    }
+    config.input = input_map.clone();
+    config.settings = settings.clone();
+    if let Err(err) = config.save() {
+        bevy::log::error!("Failed to save config: {}", err);
+    }
+}
@@ -0,0 +1,508 @@
+use std::collections::BTreeMap;
+
+use bevy::input::gamepad::GamepadAxisType;
+use bevy::input::gamepad::GamepadButtonType;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+const GAMEPAD_STICK_DEADZONE: f32 = 0.2;
+/// Stick deflection counted as a digital press for the menu actions.
+const GAMEPAD_STICK_MENU_THRESHOLD: f32 = 0.5;
+
+/// Local player, each with their own key set and gamepad.
+#[derive(
+    Component,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Reflect,
+)]
+#[reflect(Component)]
+pub enum PlayerSlot {
+    #[default]
+    One,
+    Two,
+}
+
+impl PlayerSlot {
+    pub const ALL: [PlayerSlot; 2] = [Self::One, Self::Two];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::One => "PLAYER 1",
+            Self::Two => "PLAYER 2",
+        }
+    }
+
+    /// Actions the player has bindings for. Only player one drives the pause,
+    /// the inspector and the menus.
+    pub fn actions(self) -> impl Iterator<Item = InputAction> {
+        InputAction::ALL
+            .into_iter()
+            .filter(move |action| self == Self::One || action.is_player())
+    }
+}
+
+/// Local players taking part in the encounter.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlayerMode {
+    #[default]
+    Single,
+    Coop,
+}
+
+impl PlayerMode {
+    pub fn slots(&self) -> &'static [PlayerSlot] {
+        match self {
+            Self::Single => &PlayerSlot::ALL[..1],
+            Self::Coop => &PlayerSlot::ALL,
+        }
+    }
+
+    /// Player driven by the n-th connected gamepad. In co-op the first
+    /// gamepad goes to player two, so that player one may keep the keyboard.
+    fn gamepad_slot(&self, n: usize) -> PlayerSlot {
+        match self {
+            Self::Single => PlayerSlot::One,
+            Self::Coop if n % 2 == 0 => PlayerSlot::Two,
+            Self::Coop => PlayerSlot::One,
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Reflect,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum InputAction {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Fire,
+    SecondaryFire,
+    Dash,
+    Pause,
+    ToggleInspector,
+    MenuUp,
+    MenuDown,
+    MenuNext,
+    MenuConfirm,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 13] = [
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::Fire,
+        Self::SecondaryFire,
+        Self::Dash,
+        Self::Pause,
+        Self::ToggleInspector,
+        Self::MenuUp,
+        Self::MenuDown,
+        Self::MenuNext,
+        Self::MenuConfirm,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::MoveLeft => "MOVE LEFT",
+            Self::MoveRight => "MOVE RIGHT",
+            Self::MoveUp => "MOVE UP",
+            Self::MoveDown => "MOVE DOWN",
+            Self::Fire => "FIRE",
+            Self::SecondaryFire => "SECONDARY FIRE",
+            Self::Dash => "DASH",
+            Self::Pause => "PAUSE",
+            Self::ToggleInspector => "INSPECTOR",
+            Self::MenuUp => "MENU UP",
+            Self::MenuDown => "MENU DOWN",
+            Self::MenuNext => "MENU NEXT",
+            Self::MenuConfirm => "MENU CONFIRM",
+        }
+    }
+
+    /// Actions driving a single player in the encounter.
+    fn is_player(&self) -> bool {
+        matches!(
+            self,
+            Self::MoveLeft
+                | Self::MoveRight
+                | Self::MoveUp
+                | Self::MoveDown
+                | Self::Fire
+                | Self::SecondaryFire
+                | Self::Dash
+        )
+    }
+
+    /// Menu actions are never active at the same time as the gameplay ones,
+    /// so both groups may share the same keys and buttons.
+    fn is_menu(&self) -> bool {
+        matches!(
+            self,
+            Self::MenuUp | Self::MenuDown | Self::MenuNext | Self::MenuConfirm
+        )
+    }
+
+    fn default_key(&self, slot: PlayerSlot) -> Option<KeyCode> {
+        if slot == PlayerSlot::Two {
+            return self.default_player_two_key();
+        }
+        let key = match self {
+            Self::MoveLeft => KeyCode::KeyA,
+            Self::MoveRight => KeyCode::KeyD,
+            Self::MoveUp => KeyCode::KeyW,
+            Self::MoveDown => KeyCode::KeyS,
+            Self::Fire => KeyCode::Space,
+            Self::SecondaryFire => KeyCode::KeyF,
+            Self::Dash => KeyCode::ShiftLeft,
+            Self::Pause => KeyCode::Escape,
+            Self::ToggleInspector => KeyCode::KeyI,
+            Self::MenuUp => KeyCode::ArrowUp,
+            Self::MenuDown => KeyCode::ArrowDown,
+            Self::MenuNext => KeyCode::Tab,
+            Self::MenuConfirm => KeyCode::Enter,
+        };
+        Some(key)
+    }
+
+    fn default_player_two_key(&self) -> Option<KeyCode> {
+        match self {
+            Self::MoveLeft => Some(KeyCode::ArrowLeft),
+            Self::MoveRight => Some(KeyCode::ArrowRight),
+            Self::MoveUp => Some(KeyCode::ArrowUp),
+            Self::MoveDown => Some(KeyCode::ArrowDown),
+            Self::Fire => Some(KeyCode::ControlRight),
+            Self::SecondaryFire => Some(KeyCode::ShiftRight),
+            Self::Dash => Some(KeyCode::Slash),
+            _ => None,
+        }
+    }
+
+    fn default_gamepad_button(&self) -> Option<GamepadButtonType> {
+        match self {
+            Self::MoveLeft => Some(GamepadButtonType::DPadLeft),
+            Self::MoveRight => Some(GamepadButtonType::DPadRight),
+            Self::MoveUp => Some(GamepadButtonType::DPadUp),
+            Self::MoveDown => Some(GamepadButtonType::DPadDown),
+            Self::Fire => Some(GamepadButtonType::RightTrigger2),
+            Self::SecondaryFire => Some(GamepadButtonType::LeftTrigger2),
+            Self::Dash => Some(GamepadButtonType::South),
+            Self::Pause => Some(GamepadButtonType::Start),
+            Self::ToggleInspector => None,
+            Self::MenuUp => Some(GamepadButtonType::DPadUp),
+            Self::MenuDown => Some(GamepadButtonType::DPadDown),
+            Self::MenuNext => None,
+            Self::MenuConfirm => Some(GamepadButtonType::South),
+        }
+    }
+
+    /// Left stick axis and its direction driving the action.
+    fn stick_direction(&self) -> Option<(GamepadAxisType, f32)> {
+        match self {
+            Self::MoveLeft => Some((GamepadAxisType::LeftStickX, -1.)),
+            Self::MoveRight => Some((GamepadAxisType::LeftStickX, 1.)),
+            Self::MoveUp | Self::MenuUp => Some((GamepadAxisType::LeftStickY, 1.)),
+            Self::MoveDown | Self::MenuDown => Some((GamepadAxisType::LeftStickY, -1.)),
+            _ => None,
+        }
+    }
+}
+
+/// Keys and gamepad buttons bound to the input actions. Actions missing from
+/// the maps use their default bindings. Each player has their own keys, while
+/// the gamepad buttons are the same on every gamepad. A gamepad button may be
+/// explicitly unbound, when another action took it over.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputMap {
+    keys: BTreeMap<InputAction, KeyCode>,
+    player_two_keys: BTreeMap<InputAction, KeyCode>,
+    #[serde(
+        serialize_with = "serialize_gamepad_buttons",
+        deserialize_with = "deserialize_gamepad_buttons"
+    )]
+    gamepad_buttons: BTreeMap<InputAction, Option<GamepadButtonType>>,
+    pub stick_deadzone: f32,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            keys: BTreeMap::new(),
+            player_two_keys: BTreeMap::new(),
+            gamepad_buttons: BTreeMap::new(),
+            stick_deadzone: GAMEPAD_STICK_DEADZONE,
+        }
+    }
+}
+
+impl InputMap {
+    /// Key bound to the action, `None` for the actions the player has no
+    /// bindings for.
+    pub fn key(&self, slot: PlayerSlot, action: InputAction) -> Option<KeyCode> {
+        if !slot.actions().any(|other| other == action) {
+            return None;
+        }
+        self.keys(slot)
+            .get(&action)
+            .copied()
+            .or_else(|| action.default_key(slot))
+    }
+
+    pub fn gamepad_button(&self, action: InputAction) -> Option<GamepadButtonType> {
+        match self.gamepad_buttons.get(&action) {
+            Some(button) => *button,
+            None => action.default_gamepad_button(),
+        }
+    }
+
+    /// Binds the key to the player action. If the key was bound to another
+    /// action of the same group, of either player, that action gets the
+    /// previous key of this one, so no key is bound twice.
+    pub fn bind_key(&mut self, slot: PlayerSlot, action: InputAction, key: KeyCode) {
+        let Some(previous_key) = self.key(slot, action) else {
+            return;
+        };
+        let conflicting = PlayerSlot::ALL
+            .into_iter()
+            .flat_map(|other_slot| other_slot.actions().map(move |other| (other_slot, other)))
+            .find(|&(other_slot, other)| {
+                (other_slot, other) != (slot, action)
+                    && other.is_menu() == action.is_menu()
+                    && self.key(other_slot, other) == Some(key)
+            });
+        if let Some((other_slot, other)) = conflicting {
+            self.keys_mut(other_slot).insert(other, previous_key);
+        }
+        self.keys_mut(slot).insert(action, key);
+    }
+
+    /// Same as [`InputMap::bind_key`], for the gamepad buttons.
+    pub fn bind_gamepad_button(&mut self, action: InputAction, button: GamepadButtonType) {
+        let previous_button = self.gamepad_button(action);
+        if let Some(other) =
+            Self::conflicting(action, |other| self.gamepad_button(other) == Some(button))
+        {
+            self.gamepad_buttons.insert(other, previous_button);
+        }
+        self.gamepad_buttons.insert(action, Some(button));
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn keys(&self, slot: PlayerSlot) -> &BTreeMap<InputAction, KeyCode> {
+        match slot {
+            PlayerSlot::One => &self.keys,
+            PlayerSlot::Two => &self.player_two_keys,
+        }
+    }
+
+    fn keys_mut(&mut self, slot: PlayerSlot) -> &mut BTreeMap<InputAction, KeyCode> {
+        match slot {
+            PlayerSlot::One => &mut self.keys,
+            PlayerSlot::Two => &mut self.player_two_keys,
+        }
+    }
+
+    fn conflicting(
+        action: InputAction,
+        bound: impl Fn(InputAction) -> bool,
+    ) -> Option<InputAction> {
+        InputAction::ALL
+            .into_iter()
+            .find(|other| *other != action && other.is_menu() == action.is_menu() && bound(*other))
+    }
+}
+
+/// Gamepad button binding as saved in the config file. TOML has no null
+/// value, so an unbound button is written out instead of being left out,
+/// which would bring its default back.
+#[derive(Serialize, Deserialize)]
+enum GamepadButtonBinding {
+    Unbound,
+    #[serde(untagged)]
+    Button(GamepadButtonType),
+}
+
+fn serialize_gamepad_buttons<S: Serializer>(
+    gamepad_buttons: &BTreeMap<InputAction, Option<GamepadButtonType>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(gamepad_buttons.iter().map(|(action, button)| {
+        (
+            action,
+            button.map_or(GamepadButtonBinding::Unbound, GamepadButtonBinding::Button),
+        )
+    }))
+}
+
+fn deserialize_gamepad_buttons<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<InputAction, Option<GamepadButtonType>>, D::Error> {
+    let bindings = BTreeMap::<InputAction, GamepadButtonBinding>::deserialize(deserializer)?;
+    Ok(bindings
+        .into_iter()
+        .map(|(action, binding)| match binding {
+            GamepadButtonBinding::Unbound => (action, None),
+            GamepadButtonBinding::Button(button) => (action, Some(button)),
+        })
+        .collect())
+}
+
+/// Actions of every player for the current frame, along with their analog
+/// values from `0.` to `1.`. Keys and buttons give either `0.` or `1.`, the
+/// stick gives anything in between once out of the deadzone.
+#[derive(Resource, Debug, Default)]
+pub struct PlayerActions {
+    buttons: [ButtonInput<InputAction>; 2],
+    values: [HashMap<InputAction, f32>; 2],
+}
+
+impl PlayerActions {
+    pub fn buttons(&self, slot: PlayerSlot) -> &ButtonInput<InputAction> {
+        &self.buttons[slot.index()]
+    }
+
+    pub fn value(&self, slot: PlayerSlot, action: InputAction) -> f32 {
+        self.values[slot.index()]
+            .get(&action)
+            .copied()
+            .unwrap_or(0.)
+    }
+
+    /// Value from `-1.` to `1.` of the axis made of two opposite actions.
+    pub fn axis(&self, slot: PlayerSlot, negative: InputAction, positive: InputAction) -> f32 {
+        self.value(slot, positive) - self.value(slot, negative)
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputActionSystem;
+
+/// Turns the raw keyboard and gamepad input into the actions. Gameplay reads
+/// the [`PlayerActions`], while the menus read `ButtonInput<InputAction>`,
+/// merging the actions of all the players.
+pub struct InputActionPlugin;
+
+impl Plugin for InputActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ButtonInput<InputAction>>()
+            .init_resource::<PlayerActions>()
+            .init_resource::<PlayerMode>()
+            .add_systems(
+                PreUpdate,
+                update_actions.in_set(InputActionSystem).after(InputSystem),
+            );
+    }
+}
+
+fn update_actions(
+    input_map: Res<InputMap>,
+    player_mode: Res<PlayerMode>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut actions: ResMut<ButtonInput<InputAction>>,
+    mut player_actions: ResMut<PlayerActions>,
+) {
+    let mut merged_values: HashMap<InputAction, f32> = HashMap::new();
+    for slot in PlayerSlot::ALL {
+        let state = &mut *player_actions;
+        let buttons = &mut state.buttons[slot.index()];
+        let values = &mut state.values[slot.index()];
+        buttons.clear();
+        for action in slot.actions() {
+            let mut value: f32 = 0.;
+            if let Some(key) = input_map.key(slot, action) {
+                if keyboard_input.pressed(key) {
+                    value = 1.;
+                }
+            }
+            for (n, gamepad) in gamepads.iter().enumerate() {
+                // every gamepad drives the menus, but only its own player
+                if action.is_player() && player_mode.gamepad_slot(n) != slot {
+                    continue;
+                }
+                if let Some(button) = input_map.gamepad_button(action) {
+                    if gamepad_buttons.pressed(GamepadButton::new(gamepad, button)) {
+                        value = 1.;
+                    }
+                }
+                if let Some((axis, direction)) = action.stick_direction() {
+                    let deflection = gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis))
+                        .unwrap_or(0.)
+                        * direction;
+                    value = value.max(if action.is_menu() {
+                        (deflection >= GAMEPAD_STICK_MENU_THRESHOLD) as i8 as f32
+                    } else {
+                        stick_value(deflection, input_map.stick_deadzone)
+                    });
+                }
+            }
+            values.insert(action, value);
+            update_button(buttons, action, value > 0.);
+            let merged_value = merged_values.entry(action).or_default();
+            *merged_value = merged_value.max(value);
+        }
+    }
+    actions.clear();
+    for (action, value) in merged_values {
+        update_button(&mut actions, action, value > 0.);
+    }
+}
+
+fn update_button(buttons: &mut ButtonInput<InputAction>, action: InputAction, pressed: bool) {
+    match (pressed, buttons.pressed(action)) {
+        (true, false) => buttons.press(action),
+        (false, true) => buttons.release(action),
+        _ => (),
+    }
+}
+
+/// Rescales the stick deflection beyond the deadzone back to `0.`..`1.`.
+fn stick_value(deflection: f32, deadzone: f32) -> f32 {
+    let deadzone = deadzone.clamp(0., 0.99);
+    ((deflection - deadzone) / (1. - deadzone)).clamp(0., 1.)
+}
+
+/// Run condition toggled on and off by the action, like
+/// [`bevy::input::common_conditions::input_toggle_active`] does for a key.
+pub fn action_toggle_active(
+    default: bool,
+    action: InputAction,
+) -> impl FnMut(Res<ButtonInput<InputAction>>) -> bool + Clone {
+    let mut active = default;
+    move |actions: Res<ButtonInput<InputAction>>| {
+        active ^= actions.just_pressed(action);
+        active
+    }
+}
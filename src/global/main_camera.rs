@@ -1,29 +1,96 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use rand::Rng;
+
+use super::settings::GameSettings;
+use crate::GameSystemSet;
+
+/// Pixels per second the shake amplitude fades with.
+const CAMERA_SHAKE_DECAY: f32 = 40.;
+const CAMERA_SHAKE_MAX_AMPLITUDE: f32 = 20.;
 
 #[derive(Component, Debug)]
 pub struct Tag;
 
+/// Shakes the camera by up to the given amount of pixels, unless screen shake
+/// is turned off in the settings.
+#[derive(Event, Debug)]
+pub struct CameraShakeEvent {
+    pub amplitude: f32,
+}
+
+#[derive(Resource, Debug, Default)]
+struct CameraShake {
+    amplitude: f32,
+}
+
 pub struct MainCameraPlugin;
 
 impl Plugin for MainCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, on_startup);
+        app.init_resource::<CameraShake>()
+            .add_event::<CameraShakeEvent>()
+            .add_systems(Startup, on_startup)
+            .add_systems(Update, shake_camera.in_set(GameSystemSet::Global));
     }
 }
 
 fn on_startup(windows: Query<&Window, With<PrimaryWindow>>, mut cmd: Commands) {
     let window = windows.get_single().expect("Expected primary window");
     cmd.spawn(Camera2dBundle {
-        transform: Transform {
-            translation: Vec3 {
-                x: window.resolution.width() / 2.0,
-                y: window.resolution.height() / 2.0,
-                z: 0.0,
-            },
-            ..default()
-        },
+        transform: Transform::from_translation(window_center(window)),
         ..default()
     })
     .insert(Tag);
 }
+
+/// Keeps the camera at the window center too, which follows the window
+/// resolution changed in the settings.
+fn shake_camera(
+    timer: Res<Time<Real>>,
+    settings: Res<GameSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut evr_shake: EventReader<CameraShakeEvent>,
+    mut shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<Tag>>,
+) {
+    for ev in evr_shake.read() {
+        if settings.screen_shake {
+            shake.amplitude = (shake.amplitude + ev.amplitude).min(CAMERA_SHAKE_MAX_AMPLITUDE);
+        }
+    }
+    if !settings.screen_shake {
+        shake.amplitude = 0.;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else {
+        return;
+    };
+    let offset = if shake.amplitude > 0. {
+        let amplitude = shake.amplitude;
+        shake.amplitude = (amplitude - CAMERA_SHAKE_DECAY * timer.delta_seconds()).max(0.);
+        // purely cosmetic, so it must not draw from the seeded gameplay rng
+        let mut rng = rand::thread_rng();
+        Vec3::new(
+            rng.gen_range(-amplitude..=amplitude),
+            rng.gen_range(-amplitude..=amplitude),
+            0.,
+        )
+    } else {
+        Vec3::ZERO
+    };
+    let translation = window_center(window) + offset;
+    if camera_transform.translation != translation {
+        camera_transform.translation = translation;
+    }
+}
+
+fn window_center(window: &Window) -> Vec3 {
+    Vec3 {
+        x: window.resolution.width() / 2.0,
+        y: window.resolution.height() / 2.0,
+        z: 0.0,
+    }
+}
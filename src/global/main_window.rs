@@ -2,10 +2,11 @@ use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::diagnostic::LogDiagnosticsPlugin;
 use bevy::prelude::*;
 use bevy::window::PresentMode;
+use bevy::window::PrimaryWindow;
 use bevy::window::WindowTheme;
 
-const WINDOW_WIDTH_PX: f32 = 850.0;
-const WINDOW_HEIGHT_PX: f32 = 1050.0;
+use super::settings::GameSettings;
+use crate::GameSystemSet;
 
 const WINDOW_TITLE: &str = "Pew-pew!";
 const APP_NAME: &str = "pewpew.app";
@@ -14,12 +15,20 @@ pub struct MainWindowPlugin;
 
 impl Plugin for MainWindowPlugin {
     fn build(&self, app: &mut App) {
+        // the config is loaded before, so the window opens the way it was left
+        let settings = app
+            .world
+            .get_resource::<GameSettings>()
+            .cloned()
+            .unwrap_or_default();
+        let (width, height) = settings.resolution;
         app.add_plugins((
             DefaultPlugins.set(WindowPlugin {
                 primary_window: Some(Window {
                     title: WINDOW_TITLE.into(),
                     name: Some(APP_NAME.into()),
-                    resolution: (WINDOW_WIDTH_PX, WINDOW_HEIGHT_PX).into(),
+                    resolution: (width as f32, height as f32).into(),
+                    mode: settings.display_mode.window_mode(),
                     present_mode: PresentMode::AutoVsync,
                     window_theme: Some(WindowTheme::Dark),
                     enabled_buttons: bevy::window::EnabledButtons {
@@ -33,6 +42,22 @@ impl Plugin for MainWindowPlugin {
             LogDiagnosticsPlugin::default(),
             FrameTimeDiagnosticsPlugin,
         ))
-        .insert_resource(ClearColor(Color::BLACK));
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_systems(Update, apply_window_settings.in_set(GameSystemSet::Global));
     }
 }
+
+fn apply_window_settings(
+    settings: Res<GameSettings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    let (width, height) = settings.resolution;
+    window.mode = settings.display_mode.window_mode();
+    window.resolution.set(width as f32, height as f32);
+}
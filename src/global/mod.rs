@@ -1,14 +1,26 @@
+pub mod config;
+pub mod input;
 pub mod main_camera;
 pub mod main_window;
+pub mod settings;
 
-use bevy::input::common_conditions::input_toggle_active;
 use bevy::prelude::*;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_rand::prelude::EntropyPlugin;
 use bevy_rand::prelude::WyRand;
 
+use self::config::GameConfigPlugin;
+use self::input::action_toggle_active;
+use self::input::InputAction;
+use self::input::InputActionPlugin;
 use self::main_camera::MainCameraPlugin;
 use self::main_window::MainWindowPlugin;
+use self::settings::GameSettingsPlugin;
+use crate::encounter::component::weapon::attachment::*;
+use crate::encounter::component::weapon::beam::BeamWeapon;
+use crate::encounter::component::weapon::heat::WeaponHeat;
+use crate::encounter::component::weapon::secondary::SecondaryWeapon;
+use crate::encounter::component::weapon::spread::*;
 use crate::encounter::component::weapon::WeaponShotParameters;
 use crate::encounter::component::weapon::*;
 use crate::encounter::component::*;
@@ -17,10 +29,14 @@ pub struct GlobalPlugin;
 
 impl Plugin for GlobalPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(MainWindowPlugin)
+        app.add_plugins(GameConfigPlugin)
+            .add_plugins(GameSettingsPlugin)
+            .add_plugins(InputActionPlugin)
+            .add_plugins(MainWindowPlugin)
             .add_plugins(MainCameraPlugin)
             .add_plugins(
-                WorldInspectorPlugin::new().run_if(input_toggle_active(false, KeyCode::KeyI)),
+                WorldInspectorPlugin::new()
+                    .run_if(action_toggle_active(false, InputAction::ToggleInspector)),
             )
             .add_plugins(EntropyPlugin::<WyRand>::default());
         app.register_type::<MovableX>()
@@ -28,8 +44,29 @@ impl Plugin for GlobalPlugin {
             .register_type::<MovementBound>()
             .register_type::<Speed>()
             .register_type::<EntitySize>()
-            .register_type::<Health>();
+            .register_type::<Health>()
+            .register_type::<DamageMultiplier>()
+            .register_type::<Velocity>()
+            .register_type::<Ballistics>()
+            .register_type::<Detonation>()
+            .register_type::<Ricochet>();
         app.register_type::<Weapon>()
-            .register_type::<WeaponShotParameters>();
+            .register_type::<WeaponShotParameters>()
+            .register_type::<WeaponShotDamage>()
+            .register_type::<WeaponShotDamageExplosive>()
+            .register_type::<WeaponShotDamageExplosiveDegradation>()
+            .register_type::<WeaponFireMode>()
+            .register_type::<WeaponShotResource>()
+            .register_type::<WeaponShotBallistics>()
+            .register_type::<WeaponShotRicochet>()
+            .register_type::<WeaponSpread>()
+            .register_type::<WeaponSpreadPattern>()
+            .register_type::<WeaponAttachment>()
+            .register_type::<WeaponStat>()
+            .register_type::<WeaponHeat>()
+            .register_type::<BeamWeapon>()
+            .register_type::<SecondaryWeapon>()
+            .register_type::<ShotDamage>()
+            .register_type::<WeaponBeamParameters>();
     }
 }
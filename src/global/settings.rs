@@ -0,0 +1,211 @@
+use bevy::audio::GlobalVolume;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::input::PlayerSlot;
+use crate::GameSystemSet;
+
+const VOLUME_STEPS: f32 = 10.;
+
+/// Window sizes offered by the settings menu, the first one is the default.
+pub const RESOLUTIONS: [(u32, u32); 3] = [(850, 1050), (680, 840), (1020, 1260)];
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn next(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "WINDOWED",
+            DisplayMode::Borderless => "BORDERLESS",
+            DisplayMode::Fullscreen => "FULLSCREEN",
+        }
+    }
+
+    pub fn window_mode(self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::SizedFullscreen,
+        }
+    }
+}
+
+/// Colours telling the players apart.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorPalette {
+    #[default]
+    Classic,
+    /// Orange and blue, distinguishable with the common colour blindness types.
+    HighContrast,
+}
+
+impl ColorPalette {
+    pub fn next(self) -> Self {
+        match self {
+            ColorPalette::Classic => ColorPalette::HighContrast,
+            ColorPalette::HighContrast => ColorPalette::Classic,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            ColorPalette::Classic => "CLASSIC",
+            ColorPalette::HighContrast => "HIGH CONTRAST",
+        }
+    }
+
+    pub fn player_color(self, slot: PlayerSlot) -> Color {
+        match (self, slot) {
+            (ColorPalette::Classic, PlayerSlot::One) => Color::MAROON,
+            (ColorPalette::Classic, PlayerSlot::Two) => Color::BLUE,
+            (ColorPalette::HighContrast, PlayerSlot::One) => Color::ORANGE,
+            (ColorPalette::HighContrast, PlayerSlot::Two) => Color::AZURE,
+        }
+    }
+}
+
+/// Weapon the players start the encounter with.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Loadout {
+    #[default]
+    Shotgun,
+    Rifle,
+    MachineGun,
+    Autocannon,
+    Cannon,
+    Rocket,
+    Mortar,
+    Laser,
+}
+
+impl Loadout {
+    pub fn next(self) -> Self {
+        match self {
+            Loadout::Shotgun => Loadout::Rifle,
+            Loadout::Rifle => Loadout::MachineGun,
+            Loadout::MachineGun => Loadout::Autocannon,
+            Loadout::Autocannon => Loadout::Cannon,
+            Loadout::Cannon => Loadout::Rocket,
+            Loadout::Rocket => Loadout::Mortar,
+            Loadout::Mortar => Loadout::Laser,
+            Loadout::Laser => Loadout::Shotgun,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Loadout::Shotgun => "SHOTGUN",
+            Loadout::Rifle => "RIFLE",
+            Loadout::MachineGun => "MACHINE GUN",
+            Loadout::Autocannon => "AUTOCANNON",
+            Loadout::Cannon => "CANNON",
+            Loadout::Rocket => "ROCKET",
+            Loadout::Mortar => "MORTAR",
+            Loadout::Laser => "LASER",
+        }
+    }
+}
+
+/// Player preferences changed in the settings menu. Every plugin applies the
+/// part it is responsible for, both on startup and whenever it changes.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameSettings {
+    pub master_volume: f32,
+    /// Kept for the sound effects and the music, the game has none to play
+    /// through them yet.
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub display_mode: DisplayMode,
+    pub resolution: (u32, u32),
+    pub screen_shake: bool,
+    pub damage_numbers: bool,
+    pub palette: ColorPalette,
+    /// Pauses the encounter when the game window is left.
+    pub auto_pause: bool,
+    /// Applies from the next encounter on.
+    pub loadout: Loadout,
+    /// Lets the players also move up and down within the lower band of the
+    /// arena.
+    pub vertical_movement: bool,
+    /// Player one aims the turret with the mouse instead of shooting straight
+    /// up.
+    pub mouse_aim: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.,
+            sfx_volume: 1.,
+            music_volume: 1.,
+            display_mode: DisplayMode::default(),
+            resolution: RESOLUTIONS[0],
+            screen_shake: true,
+            damage_numbers: true,
+            palette: ColorPalette::default(),
+            auto_pause: true,
+            loadout: Loadout::default(),
+            vertical_movement: false,
+            mouse_aim: false,
+        }
+    }
+}
+
+impl GameSettings {
+    /// Turns the volume up by a step, wrapping from the full volume to mute.
+    pub fn next_volume(volume: f32) -> f32 {
+        let step = (volume * VOLUME_STEPS).round() + 1.;
+        if step > VOLUME_STEPS {
+            0.
+        } else {
+            step / VOLUME_STEPS
+        }
+    }
+
+    /// Switches to the next preset, or to the default one if the resolution
+    /// was edited by hand.
+    pub fn next_resolution(&mut self) {
+        let next = RESOLUTIONS
+            .iter()
+            .position(|resolution| *resolution == self.resolution)
+            .map_or(0, |n| (n + 1) % RESOLUTIONS.len());
+        self.resolution = RESOLUTIONS[next];
+    }
+}
+
+pub struct GameSettingsPlugin;
+
+impl Plugin for GameSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameSettings>()
+            .add_systems(Update, apply_audio_settings.in_set(GameSystemSet::Global));
+    }
+}
+
+/// Only the master volume has something to apply to, the game has no sound
+/// effects or music yet.
+fn apply_audio_settings(settings: Res<GameSettings>, mut global_volume: ResMut<GlobalVolume>) {
+    if !settings.is_changed() {
+        return;
+    }
+    global_volume.volume = Volume::new(settings.master_volume);
+}
@@ -1,19 +1,29 @@
+// Bevy systems routinely have complex queries and many parameters.
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+pub mod controls;
 pub mod encounter;
 pub mod game_over;
 pub mod global;
 pub mod main_menu;
+pub mod menu;
 pub mod pause;
+pub mod settings;
 pub mod util;
 
 use bevy::prelude::*;
+use controls::ui::ControlsUIPlugin;
 use game_over::ui::GameOverUIPlugin;
 use pause::transition::PauseState;
+use settings::transition::SettingsState;
 use state::GameState;
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GameSystemSet {
     Global,
     MainMenu,
+    Settings,
+    Controls,
     Encounter,
     EncounterPausable,
     GameOver,
@@ -28,6 +38,12 @@ fn main() {
             GameSystemSet::MainMenu
                 .run_if(in_state(GameState::MainMenu))
                 .after(GameSystemSet::Global),
+            GameSystemSet::Settings
+                .run_if(in_state(SettingsState::Settings))
+                .after(GameSystemSet::Global),
+            GameSystemSet::Controls
+                .run_if(in_state(SettingsState::Controls))
+                .after(GameSystemSet::Global),
             GameSystemSet::Encounter
                 .run_if(in_state(GameState::Encounter))
                 .after(GameSystemSet::Global),
@@ -44,6 +60,12 @@ fn main() {
             GameSystemSet::MainMenu
                 .run_if(in_state(GameState::MainMenu))
                 .after(GameSystemSet::Global),
+            GameSystemSet::Settings
+                .run_if(in_state(SettingsState::Settings))
+                .after(GameSystemSet::Global),
+            GameSystemSet::Controls
+                .run_if(in_state(SettingsState::Controls))
+                .after(GameSystemSet::Global),
             GameSystemSet::Encounter
                 .run_if(in_state(GameState::Encounter))
                 .after(GameSystemSet::Global),
@@ -60,6 +82,12 @@ fn main() {
             GameSystemSet::MainMenu
                 .run_if(in_state(GameState::MainMenu))
                 .after(GameSystemSet::Global),
+            GameSystemSet::Settings
+                .run_if(in_state(SettingsState::Settings))
+                .after(GameSystemSet::Global),
+            GameSystemSet::Controls
+                .run_if(in_state(SettingsState::Controls))
+                .after(GameSystemSet::Global),
             GameSystemSet::Encounter
                 .run_if(in_state(GameState::Encounter))
                 .after(GameSystemSet::Global),
@@ -73,6 +101,9 @@ fn main() {
     app.add_plugins(global::GlobalPlugin)
         .add_plugins(transition::GameStateTransitionPlugin)
         .add_plugins(main_menu::ui::MainMenuUIPlugin)
+        .add_plugins(menu::MenuPlugin)
+        .add_plugins(settings::SettingsPlugin)
+        .add_plugins(ControlsUIPlugin)
         .add_plugins(encounter::EncounterPlugin)
         .add_plugins(pause::PausePlugin)
         .add_plugins(GameOverUIPlugin);
@@ -86,12 +117,14 @@ pub mod transition {
 
     use super::state::GameState;
     use super::GameSystemSet;
+    use crate::game_over::GameOverReason;
 
-    #[derive(Event, Debug)]
+    #[derive(Event, Debug, Clone)]
     pub enum GameStateTransitionEvent {
         StartEncounter,
+        RestartEncounter,
         QuitEncounter,
-        GameOver,
+        GameOver(GameOverReason),
         QuitGame,
     }
 
@@ -100,6 +133,7 @@ pub mod transition {
     impl Plugin for GameStateTransitionPlugin {
         fn build(&self, app: &mut App) {
             app.init_state::<GameState>()
+                .add_systems(OnEnter(GameState::Restart), restart_encounter)
                 .add_systems(
                     Update,
                     handle_game_state_transition.in_set(GameSystemSet::Global),
@@ -109,6 +143,7 @@ pub mod transition {
     }
 
     fn handle_game_state_transition(
+        mut cmd: Commands,
         state: Res<State<GameState>>,
         mut evr_transition: EventReader<GameStateTransitionEvent>,
         mut next_state: ResMut<NextState<GameState>>,
@@ -125,10 +160,14 @@ pub mod transition {
             (GameState::MainMenu, GameStateTransitionEvent::QuitGame) => {
                 evw_exit.send(AppExit);
             }
+            (GameState::Encounter, GameStateTransitionEvent::RestartEncounter) => {
+                next_state.set(GameState::Restart);
+            }
             (GameState::Encounter, GameStateTransitionEvent::QuitEncounter) => {
                 next_state.set(GameState::MainMenu);
             }
-            (GameState::Encounter, GameStateTransitionEvent::GameOver) => {
+            (GameState::Encounter, GameStateTransitionEvent::GameOver(reason)) => {
+                cmd.insert_resource(*reason);
                 next_state.set(GameState::GameOver);
             }
             (GameState::GameOver, GameStateTransitionEvent::QuitEncounter) => {
@@ -142,6 +181,10 @@ pub mod transition {
             }
         };
     }
+
+    fn restart_encounter(mut next_state: ResMut<NextState<GameState>>) {
+        next_state.set(GameState::Encounter);
+    }
 }
 
 pub mod state {
@@ -152,6 +195,9 @@ pub mod state {
         #[default]
         MainMenu,
         Encounter,
+        /// Left for a new encounter right away. Setting the encounter state
+        /// again would not run its exit and enter schedules, this does.
+        Restart,
         GameOver,
     }
 }
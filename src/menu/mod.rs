@@ -0,0 +1,372 @@
+pub mod navigation;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use self::navigation::Menu;
+use self::navigation::MenuNavigationPlugin;
+use crate::GameSystemSet;
+
+const ROOT_NODE_COLOR: Color = Color::BLACK;
+const BUTTON_NORMAL_COLOR: Color = Color::BLACK;
+const BUTTON_HOVER_COLOR: Color = Color::MAROON;
+const BUTTON_PRESSED_COLOR: Color = Color::CRIMSON;
+const SUBTITLE_TEXT_COLOR: Color = Color::MAROON;
+
+const SUBTITLE_TEXT_SIZE: f32 = 30.;
+
+/// Layer of the menus shown over the game.
+pub const MENU_LAYER: i32 = 5;
+/// Layer of the menus opened on top of another menu.
+pub const OVERLAY_MENU_LAYER: i32 = 10;
+
+/// Sizes of the menu elements, in percents of the screen unless told
+/// otherwise.
+struct MenuMetrics {
+    padding: f32,
+    title_height: f32,
+    title_text_size: f32,
+    button_width: f32,
+    button_height: f32,
+    button_margin: f32,
+    button_border_px: f32,
+    button_text_size: f32,
+}
+
+const REGULAR_METRICS: MenuMetrics = MenuMetrics {
+    padding: 20.,
+    title_height: 30.,
+    title_text_size: 100.,
+    button_width: 80.,
+    button_height: 12.,
+    button_margin: 2.,
+    button_border_px: 10.,
+    button_text_size: 50.,
+};
+
+/// Fits about fifteen buttons on the screen.
+const COMPACT_METRICS: MenuMetrics = MenuMetrics {
+    padding: 5.,
+    title_height: 15.,
+    title_text_size: 60.,
+    button_width: 80.,
+    button_height: 5.,
+    button_margin: 0.6,
+    button_border_px: 4.,
+    button_text_size: 26.,
+};
+
+/// Full width buttons with small text, for the long lists like the key
+/// bindings.
+const DENSE_METRICS: MenuMetrics = MenuMetrics {
+    padding: 5.,
+    title_height: 10.,
+    title_text_size: 60.,
+    button_width: 100.,
+    button_height: 4.2,
+    button_margin: 0.4,
+    button_border_px: 4.,
+    button_text_size: 20.,
+};
+
+/// What pressing a menu button does.
+#[derive(Component)]
+pub struct MenuButtonAction(Box<dyn Fn(&mut Commands) + Send + Sync>);
+
+/// Ties the menu to the state it is shown in.
+#[derive(Component, Debug)]
+pub struct MenuScope<S: States>(S);
+
+struct MenuButton {
+    text: &'static str,
+    action: MenuButtonAction,
+    /// Inserts the component the menu looks the button text up with.
+    label: Option<Box<dyn FnOnce(&mut EntityCommands) + Send + Sync>>,
+}
+
+/// Menu declared as a title, an optional subtitle and a column of buttons.
+/// Styling and layout are shared by all the menus built with it.
+pub struct MenuBuilder {
+    name: &'static str,
+    title: &'static str,
+    subtitle: Option<String>,
+    background: Color,
+    layer: i32,
+    metrics: &'static MenuMetrics,
+    buttons: Vec<MenuButton>,
+}
+
+impl MenuBuilder {
+    /// The name keys the focus remembered by the menu navigation.
+    pub fn new(name: &'static str, title: &'static str) -> Self {
+        Self {
+            name,
+            title,
+            subtitle: None,
+            background: ROOT_NODE_COLOR,
+            layer: MENU_LAYER,
+            metrics: &REGULAR_METRICS,
+            buttons: vec![],
+        }
+    }
+
+    pub fn subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Menus on the higher layer cover the ones below, and take the
+    /// navigation over from them.
+    pub fn layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Smaller title and buttons, for the menus with many buttons.
+    pub fn compact(mut self) -> Self {
+        self.metrics = &COMPACT_METRICS;
+        self
+    }
+
+    /// Smallest buttons, for the menus listing a button per input action.
+    pub fn dense(mut self) -> Self {
+        self.metrics = &DENSE_METRICS;
+        self
+    }
+
+    pub fn button(
+        mut self,
+        text: &'static str,
+        action: impl Fn(&mut Commands) + Send + Sync + 'static,
+    ) -> Self {
+        self.buttons.push(MenuButton {
+            text,
+            action: MenuButtonAction(Box::new(action)),
+            label: None,
+        });
+        self
+    }
+
+    /// Button whose text is written by the menu itself, looking it up with
+    /// the label component.
+    pub fn label_button(
+        mut self,
+        label: impl Component,
+        action: impl Fn(&mut Commands) + Send + Sync + 'static,
+    ) -> Self {
+        self.buttons.push(MenuButton {
+            text: "",
+            action: MenuButtonAction(Box::new(action)),
+            label: Some(Box::new(move |text| {
+                text.insert(label);
+            })),
+        });
+        self
+    }
+
+    /// Button sending the event when pressed.
+    pub fn event_button<E: Event + Clone>(self, text: &'static str, event: E) -> Self {
+        self.button(text, move |cmd| {
+            let event = event.clone();
+            cmd.add(move |world: &mut World| {
+                world.send_event(event);
+            });
+        })
+    }
+
+    pub fn spawn(self, cmd: &mut Commands) -> Entity {
+        let Self {
+            name,
+            title,
+            subtitle,
+            background,
+            layer,
+            metrics,
+            buttons,
+        } = self;
+        cmd.spawn(root_node(background, layer, metrics))
+            .with_children(|root| {
+                root.spawn(wrapper_node()).with_children(|wrapper| {
+                    menu_title(wrapper, title, metrics);
+                    if let Some(subtitle) = subtitle {
+                        menu_subtitle(wrapper, subtitle);
+                    }
+                    for button in buttons {
+                        menu_button(wrapper, button, metrics);
+                    }
+                });
+            })
+            .insert(Menu(name))
+            .id()
+    }
+}
+
+pub trait MenuAppExt {
+    /// Spawns the menu built by the system when entering the state, and
+    /// despawns it when exiting the state.
+    fn add_menu<S: States, M>(
+        &mut self,
+        state: S,
+        menu: impl IntoSystem<(), MenuBuilder, M>,
+    ) -> &mut Self;
+}
+
+impl MenuAppExt for App {
+    fn add_menu<S: States, M>(
+        &mut self,
+        state: S,
+        menu: impl IntoSystem<(), MenuBuilder, M>,
+    ) -> &mut Self {
+        let scope = state.clone();
+        let spawn_menu = move |In(menu): In<MenuBuilder>, mut cmd: Commands| {
+            let root = menu.spawn(&mut cmd);
+            cmd.entity(root).insert(MenuScope(scope.clone()));
+        };
+        let scope = state.clone();
+        let despawn_menu = move |mut cmd: Commands, query: Query<(Entity, &MenuScope<S>)>| {
+            for (menu, menu_scope) in query.iter() {
+                if menu_scope.0 == scope {
+                    cmd.entity(menu).despawn_recursive();
+                }
+            }
+        };
+        self.add_systems(OnEnter(state.clone()), menu.pipe(spawn_menu))
+            .add_systems(OnExit(state), despawn_menu)
+    }
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MenuNavigationPlugin)
+            .add_systems(Update, menu_button_handler.in_set(GameSystemSet::Global));
+    }
+}
+
+fn menu_button_handler(
+    mut cmd: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &MenuButtonAction),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut color, action) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = BUTTON_PRESSED_COLOR.into();
+                (action.0)(&mut cmd);
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+fn root_node(background: Color, layer: i32, metrics: &MenuMetrics) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            padding: UiRect {
+                left: Val::Percent(20.0),
+                right: Val::Percent(20.0),
+                top: Val::Percent(metrics.padding),
+                bottom: Val::Percent(metrics.padding),
+            },
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        background_color: background.into(),
+        // the menus below must not be clicked through this one
+        focus_policy: FocusPolicy::Block,
+        z_index: ZIndex::Global(layer),
+        ..default()
+    }
+}
+
+fn wrapper_node() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+fn menu_title(root: &mut ChildBuilder, title: &'static str, metrics: &MenuMetrics) {
+    root.spawn(NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(metrics.title_height),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ..default()
+    })
+    .with_children(|title_node| {
+        title_node.spawn(TextBundle::from_section(title, TextStyle {
+            font_size: metrics.title_text_size,
+            color: Color::WHITE,
+            ..default()
+        }));
+    });
+}
+
+fn menu_subtitle(root: &mut ChildBuilder, subtitle: String) {
+    root.spawn(
+        TextBundle::from_section(subtitle, TextStyle {
+            font_size: SUBTITLE_TEXT_SIZE,
+            color: SUBTITLE_TEXT_COLOR,
+            ..default()
+        })
+        .with_style(Style {
+            margin: UiRect::bottom(Val::Percent(5.0)),
+            ..default()
+        }),
+    );
+}
+
+fn menu_button(root: &mut ChildBuilder, button: MenuButton, metrics: &MenuMetrics) {
+    root.spawn(ButtonBundle {
+        style: Style {
+            width: Val::Percent(metrics.button_width),
+            height: Val::Percent(metrics.button_height),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            margin: UiRect::all(Val::Percent(metrics.button_margin)),
+            border: UiRect::all(Val::Px(metrics.button_border_px)),
+            ..default()
+        },
+        background_color: BUTTON_NORMAL_COLOR.into(),
+        border_color: BUTTON_HOVER_COLOR.into(),
+        ..default()
+    })
+    .insert(button.action)
+    .with_children(|button_node| {
+        let mut text = button_node.spawn(TextBundle::from_section(button.text, TextStyle {
+            font_size: metrics.button_text_size,
+            color: Color::WHITE,
+            ..default()
+        }));
+        if let Some(label) = button.label {
+            label(&mut text);
+        }
+    });
+}
@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use bevy::utils::HashMap;
+
+use crate::global::input::InputAction;
+use crate::global::input::InputActionSystem;
+
+const FOCUS_OUTLINE_COLOR: Color = Color::WHITE;
+const FOCUS_OUTLINE_WIDTH_PX: f32 = 4.;
+
+/// Root of a menu navigable with the menu actions. The name keys the focus
+/// remembered for the next time the menu opens.
+#[derive(Component, Debug)]
+pub struct Menu(pub &'static str);
+
+/// Menu button focused with the menu actions.
+///
+/// The buttons are ordered the way they are laid out on the screen, top to
+/// bottom, so any visible menu can be navigated without extra setup. Only
+/// the menus on the top layer are navigated, the ones they cover are not. The
+/// confirm action presses the focused button the same way the mouse does,
+/// so the menus handle it with their usual `Interaction` handlers.
+#[derive(Resource, Debug, Default)]
+pub struct MenuFocus {
+    focused: Option<Entity>,
+    /// Button pressed by the confirm action, released the next frame.
+    pressed: Option<Entity>,
+    /// Position of the last focused button of every menu.
+    remembered: HashMap<&'static str, usize>,
+    /// Suspends the navigation while a menu reads the raw input itself.
+    pub locked: bool,
+}
+
+pub struct MenuNavigationPlugin;
+
+impl Plugin for MenuNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuFocus>().add_systems(
+            PreUpdate,
+            navigate_menus
+                .after(InputActionSystem)
+                .after(UiSystem::Focus),
+        );
+    }
+}
+
+fn navigate_menus(
+    mut cmd: Commands,
+    actions: Res<ButtonInput<InputAction>>,
+    mut focus: ResMut<MenuFocus>,
+    mut buttons_query: Query<
+        (
+            Entity,
+            &Node,
+            &GlobalTransform,
+            &ViewVisibility,
+            &mut Interaction,
+        ),
+        With<Button>,
+    >,
+    menus_query: Query<(&Menu, Option<&ZIndex>)>,
+    parents_query: Query<&Parent>,
+) {
+    if let Some(pressed) = focus.pressed.take() {
+        if let Ok((_, _, _, _, mut interaction)) = buttons_query.get_mut(pressed) {
+            if *interaction == Interaction::Pressed {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+
+    // the mouse moves the focus too, so both never highlight different buttons
+    let mut buttons: Vec<(Entity, Vec3, bool, Option<(&'static str, i32)>)> = buttons_query
+        .iter_mut()
+        // hidden buttons have no size
+        .filter(|(_, node, _, visibility, _)| visibility.get() && node.size() != Vec2::ZERO)
+        .map(|(entity, _, transform, _, interaction)| {
+            let hovered = interaction.is_changed() && *interaction == Interaction::Hovered;
+            let menu = menu_of(entity, &menus_query, &parents_query);
+            (entity, transform.translation(), hovered, menu)
+        })
+        .collect();
+    let layer_of = |menu: &Option<(&'static str, i32)>| menu.map_or(0, |(_, layer)| layer);
+    let top_layer = buttons.iter().map(|(_, _, _, menu)| layer_of(menu)).max();
+    buttons.retain(|(_, _, _, menu)| Some(layer_of(menu)) == top_layer);
+    if buttons.is_empty() {
+        focus.focused = None;
+        return;
+    }
+    buttons.sort_by(|(_, a, _, _), (_, b, _, _)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    let len = buttons.len();
+    let menu = buttons[0].3.map(|(name, _)| name);
+    let current = focus.focused.and_then(|focused| {
+        buttons
+            .iter()
+            .position(|(entity, _, _, _)| *entity == focused)
+    });
+    if focus.locked {
+        return;
+    }
+
+    let hovered = buttons.iter().position(|(_, _, hovered, _)| *hovered);
+    let next = if actions.just_pressed(InputAction::MenuDown)
+        || actions.just_pressed(InputAction::MenuNext)
+    {
+        Some(current.map_or(0, |n| (n + 1) % len))
+    } else if actions.just_pressed(InputAction::MenuUp) {
+        Some(current.map_or(len - 1, |n| (n + len - 1) % len))
+    } else if hovered.is_some() {
+        hovered
+    } else if current.is_none() {
+        // a menu just opened, back to where it was left
+        menu.and_then(|menu| focus.remembered.get(menu).copied())
+            .filter(|n| *n < len)
+    } else {
+        current
+    };
+    if let (Some(menu), Some(next)) = (menu, next) {
+        focus.remembered.insert(menu, next);
+    }
+    let next = next.map(|n| buttons[n].0);
+    if next != focus.focused {
+        // the previous button may be left in a covered menu, or despawned
+        if let Some(previous) = focus.focused.filter(|e| buttons_query.contains(*e)) {
+            cmd.entity(previous).remove::<Outline>();
+        }
+        if let Some(next) = next {
+            cmd.entity(next).insert(Outline::new(
+                Val::Px(FOCUS_OUTLINE_WIDTH_PX),
+                Val::ZERO,
+                FOCUS_OUTLINE_COLOR,
+            ));
+        }
+        focus.focused = next;
+    }
+
+    if actions.just_pressed(InputAction::MenuConfirm) {
+        let Some(focused) = focus.focused else {
+            return;
+        };
+        if let Ok((_, _, _, _, mut interaction)) = buttons_query.get_mut(focused) {
+            *interaction = Interaction::Pressed;
+            focus.pressed = Some(focused);
+        }
+    }
+}
+
+/// Name and layer of the menu the button belongs to.
+fn menu_of(
+    button: Entity,
+    menus_query: &Query<(&Menu, Option<&ZIndex>)>,
+    parents_query: &Query<&Parent>,
+) -> Option<(&'static str, i32)> {
+    std::iter::once(button)
+        .chain(parents_query.iter_ancestors(button))
+        .find_map(|entity| menus_query.get(entity).ok())
+        .map(|(menu, z_index)| {
+            let layer = match z_index {
+                Some(ZIndex::Global(layer)) => *layer,
+                _ => 0,
+            };
+            (menu.0, layer)
+        })
+}
@@ -15,15 +15,23 @@ impl Plugin for PausePlugin {
 }
 
 pub mod transition {
+    use bevy::ecs::schedule::apply_state_transition;
     use bevy::ecs::schedule::States;
     use bevy::prelude::*;
 
     use crate::state::GameState;
     use crate::GameSystemSet;
 
+    /// Pause of the encounter, behaving as its sub-state: it is `Disabled`
+    /// everywhere but in the encounter, which always starts `Running`.
+    ///
+    /// The gameplay runs on the virtual time, which stands still while the
+    /// encounter is paused, so every timer freezes with it, wherever it is
+    /// ticked.
     #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
     pub enum PauseState {
         #[default]
+        Disabled,
         Running,
         Pause,
     }
@@ -33,7 +41,14 @@ pub mod transition {
     impl Plugin for PauseStateTransitionPlugin {
         fn build(&self, app: &mut App) {
             app.init_state::<PauseState>()
-                .add_systems(OnExit(GameState::Encounter), on_exit_encounter)
+                .add_systems(
+                    StateTransition,
+                    follow_game_state
+                        .after(apply_state_transition::<GameState>)
+                        .before(apply_state_transition::<PauseState>),
+                )
+                .add_systems(OnEnter(PauseState::Pause), pause_time)
+                .add_systems(OnExit(PauseState::Pause), unpause_time)
                 .add_systems(
                     Update,
                     handle_encounter_pause_transition.in_set(GameSystemSet::Encounter),
@@ -42,23 +57,32 @@ pub mod transition {
         }
     }
 
-    /// This system ensures that player will not leave the encounter with pause
-    /// turned on.
-    ///
-    /// The reason for this workaround is that GameState and PauseState are
-    /// completely independent for now, so there are no appropriate ways to
-    /// switch the PauseState based on GameState, except this one.
-    ///
-    /// If you turn this system off, then, if player exits the encounter, pause
-    /// menu will hang on the screen forever.
-    ///
-    /// TODO: This may be fixed when sub-states support will land:
-    /// https://github.com/bevyengine/bevy/pull/11426
-    fn on_exit_encounter(mut next_state: ResMut<NextState<PauseState>>) {
-        next_state.set(PauseState::Running);
+    /// Enters and leaves the pause state along with the encounter, within the
+    /// same state transition, so the pause menu can never outlive it.
+    fn follow_game_state(
+        game_state: Res<State<GameState>>,
+        state: Res<State<PauseState>>,
+        mut next_state: ResMut<NextState<PauseState>>,
+    ) {
+        let in_encounter = *game_state.get() == GameState::Encounter;
+        match (in_encounter, state.get()) {
+            (true, PauseState::Disabled) => next_state.set(PauseState::Running),
+            (false, PauseState::Running | PauseState::Pause) => {
+                next_state.set(PauseState::Disabled)
+            }
+            _ => {}
+        }
+    }
+
+    fn pause_time(mut time: ResMut<Time<Virtual>>) {
+        time.pause();
+    }
+
+    fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+        time.unpause();
     }
 
-    #[derive(Event, Debug)]
+    #[derive(Event, Debug, Clone)]
     pub struct EncounterPauseStateTransitionEvent;
 
     fn handle_encounter_pause_transition(
@@ -77,6 +101,9 @@ pub mod transition {
                     bevy::log::info!("Setting game running");
                     next_state.set(PauseState::Running)
                 }
+                PauseState::Disabled => {
+                    bevy::log::warn!("Got pause transition outside of the encounter");
+                }
             }
         }
     }
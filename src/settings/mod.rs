@@ -0,0 +1,107 @@
+pub mod ui;
+
+use bevy::prelude::*;
+
+use self::transition::SettingsStateTransitionPlugin;
+use self::ui::SettingsMenuUIPlugin;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SettingsStateTransitionPlugin)
+            .add_plugins(SettingsMenuUIPlugin);
+    }
+}
+
+pub mod transition {
+    use bevy::prelude::*;
+
+    use crate::global::input::InputAction;
+    use crate::menu::navigation::MenuFocus;
+    use crate::pause::transition::PauseState;
+    use crate::state::GameState;
+    use crate::GameSystemSet;
+
+    /// Settings screens opened over the main menu or the pause menu.
+    #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+    pub enum SettingsState {
+        #[default]
+        Closed,
+        Settings,
+        Controls,
+    }
+
+    #[derive(Event, Debug, Clone)]
+    pub enum SettingsStateTransitionEvent {
+        Open,
+        OpenControls,
+        Back,
+    }
+
+    pub struct SettingsStateTransitionPlugin;
+
+    impl Plugin for SettingsStateTransitionPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_state::<SettingsState>()
+                .add_systems(OnExit(GameState::MainMenu), close_settings)
+                .add_systems(OnExit(PauseState::Pause), close_settings)
+                .add_systems(
+                    Update,
+                    (back_controls_handler, handle_settings_state_transition)
+                        .chain()
+                        .in_set(GameSystemSet::Global),
+                )
+                .add_event::<SettingsStateTransitionEvent>();
+        }
+    }
+
+    /// The settings never outlive the menu they were opened from.
+    fn close_settings(mut next_state: ResMut<NextState<SettingsState>>) {
+        next_state.set(SettingsState::Closed);
+    }
+
+    /// The pause action leaves the settings the same way it leaves the pause
+    /// menu, unless it is being bound to a key.
+    fn back_controls_handler(
+        state: Res<State<SettingsState>>,
+        actions: Res<ButtonInput<InputAction>>,
+        menu_focus: Res<MenuFocus>,
+        mut evw_transition: EventWriter<SettingsStateTransitionEvent>,
+    ) {
+        if *state.get() != SettingsState::Closed
+            && !menu_focus.locked
+            && actions.just_pressed(InputAction::Pause)
+        {
+            evw_transition.send(SettingsStateTransitionEvent::Back);
+        }
+    }
+
+    fn handle_settings_state_transition(
+        state: Res<State<SettingsState>>,
+        mut evr_transition: EventReader<SettingsStateTransitionEvent>,
+        mut next_state: ResMut<NextState<SettingsState>>,
+    ) {
+        let state = state.get();
+        let Some(ev) = evr_transition.read().next() else {
+            return;
+        };
+        match (state, ev) {
+            (SettingsState::Closed, SettingsStateTransitionEvent::Open) => {
+                next_state.set(SettingsState::Settings);
+            }
+            (SettingsState::Settings, SettingsStateTransitionEvent::OpenControls) => {
+                next_state.set(SettingsState::Controls);
+            }
+            (SettingsState::Controls, SettingsStateTransitionEvent::Back) => {
+                next_state.set(SettingsState::Settings);
+            }
+            (SettingsState::Settings, SettingsStateTransitionEvent::Back) => {
+                next_state.set(SettingsState::Closed);
+            }
+            (state_, ev_) => {
+                bevy::log::warn!("Got invalid {:?} while in state {:?}", ev_, state_);
+            }
+        };
+    }
+}
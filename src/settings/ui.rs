@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+
+use super::transition::SettingsState;
+use super::transition::SettingsStateTransitionEvent;
+use crate::global::settings::GameSettings;
+use crate::menu::MenuAppExt;
+use crate::menu::MenuBuilder;
+use crate::menu::OVERLAY_MENU_LAYER;
+use crate::GameSystemSet;
+
+const TITLE_TEXT: &str = "SETTINGS";
+const KEY_BINDINGS_BUTTON_TEXT: &str = "KEY BINDINGS";
+const BACK_BUTTON_TEXT: &str = "BACK";
+const ON_TEXT: &str = "ON";
+const OFF_TEXT: &str = "OFF";
+
+/// Setting shown on the button text.
+#[derive(Component, Debug, Clone, Copy)]
+pub enum SettingLabel {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    DisplayMode,
+    Resolution,
+    ScreenShake,
+    DamageNumbers,
+    Palette,
+    AutoPause,
+    Loadout,
+    VerticalMovement,
+    MouseAim,
+}
+
+impl SettingLabel {
+    fn text(self, settings: &GameSettings) -> String {
+        let toggle = |enabled: bool| if enabled { ON_TEXT } else { OFF_TEXT };
+        let volume = |volume: f32| format!("{:.0}%", volume * 100.);
+        match self {
+            SettingLabel::MasterVolume => format!("VOLUME: {}", volume(settings.master_volume)),
+            SettingLabel::SfxVolume => format!("SFX: {}", volume(settings.sfx_volume)),
+            SettingLabel::MusicVolume => format!("MUSIC: {}", volume(settings.music_volume)),
+            SettingLabel::DisplayMode => format!("WINDOW: {}", settings.display_mode.title()),
+            SettingLabel::Resolution => {
+                let (width, height) = settings.resolution;
+                format!("RESOLUTION: {}x{}", width, height)
+            }
+            SettingLabel::ScreenShake => format!("SCREEN SHAKE: {}", toggle(settings.screen_shake)),
+            SettingLabel::DamageNumbers => {
+                format!("DAMAGE NUMBERS: {}", toggle(settings.damage_numbers))
+            }
+            SettingLabel::Palette => format!("COLOURS: {}", settings.palette.title()),
+            SettingLabel::AutoPause => format!("AUTO PAUSE: {}", toggle(settings.auto_pause)),
+            SettingLabel::Loadout => format!("WEAPON: {}", settings.loadout.title()),
+            SettingLabel::VerticalMovement => {
+                format!("VERTICAL MOVEMENT: {}", toggle(settings.vertical_movement))
+            }
+            SettingLabel::MouseAim => format!("MOUSE AIM: {}", toggle(settings.mouse_aim)),
+        }
+    }
+}
+
+pub struct SettingsMenuUIPlugin;
+
+impl Plugin for SettingsMenuUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_menu(SettingsState::Settings, settings_menu)
+            .add_systems(Update, redraw_settings.in_set(GameSystemSet::Settings));
+    }
+}
+
+fn settings_menu() -> MenuBuilder {
+    MenuBuilder::new("settings", TITLE_TEXT)
+        .layer(OVERLAY_MENU_LAYER)
+        .compact()
+        .label_button(
+            SettingLabel::MasterVolume,
+            change_setting(|s| s.master_volume = GameSettings::next_volume(s.master_volume)),
+        )
+        .label_button(
+            SettingLabel::SfxVolume,
+            change_setting(|s| s.sfx_volume = GameSettings::next_volume(s.sfx_volume)),
+        )
+        .label_button(
+            SettingLabel::MusicVolume,
+            change_setting(|s| s.music_volume = GameSettings::next_volume(s.music_volume)),
+        )
+        .label_button(
+            SettingLabel::DisplayMode,
+            change_setting(|s| s.display_mode = s.display_mode.next()),
+        )
+        .label_button(
+            SettingLabel::Resolution,
+            change_setting(GameSettings::next_resolution),
+        )
+        .label_button(
+            SettingLabel::ScreenShake,
+            change_setting(|s| s.screen_shake = !s.screen_shake),
+        )
+        .label_button(
+            SettingLabel::DamageNumbers,
+            change_setting(|s| s.damage_numbers = !s.damage_numbers),
+        )
+        .label_button(
+            SettingLabel::Palette,
+            change_setting(|s| s.palette = s.palette.next()),
+        )
+        .label_button(
+            SettingLabel::AutoPause,
+            change_setting(|s| s.auto_pause = !s.auto_pause),
+        )
+        .label_button(
+            SettingLabel::Loadout,
+            change_setting(|s| s.loadout = s.loadout.next()),
+        )
+        .label_button(
+            SettingLabel::VerticalMovement,
+            change_setting(|s| s.vertical_movement = !s.vertical_movement),
+        )
+        .label_button(
+            SettingLabel::MouseAim,
+            change_setting(|s| s.mouse_aim = !s.mouse_aim),
+        )
+        .event_button(
+            KEY_BINDINGS_BUTTON_TEXT,
+            SettingsStateTransitionEvent::OpenControls,
+        )
+        .event_button(BACK_BUTTON_TEXT, SettingsStateTransitionEvent::Back)
+}
+
+fn change_setting(change: fn(&mut GameSettings)) -> impl Fn(&mut Commands) + Send + Sync {
+    move |cmd| {
+        cmd.add(move |world: &mut World| {
+            change(&mut world.resource_mut::<GameSettings>());
+        });
+    }
+}
+
+fn redraw_settings(settings: Res<GameSettings>, mut text_query: Query<(&mut Text, &SettingLabel)>) {
+    for (mut text, label) in text_query.iter_mut() {
+        let value = label.text(&settings);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
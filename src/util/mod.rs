@@ -14,8 +14,8 @@ impl ColorHex {
     }
 }
 
-impl Into<Color> for ColorHex {
-    fn into(self) -> Color {
-        self.as_rgba()
+impl From<ColorHex> for Color {
+    fn from(value: ColorHex) -> Self {
+        value.as_rgba()
     }
 }